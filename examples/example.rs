@@ -11,6 +11,10 @@ fn main() {
     // Get the name of a variant
     assert_eq!(Color::Blue.name(), "Blue");
 
+    // Convert from name back to variant
+    assert_eq!(Color::from_name("Green"), Some(Color::Green));
+    assert_eq!(Color::from_name("Purple"), None);
+
     // Get the ordinal (position) of a variant
     assert_eq!(Color::Green.ordinal(), 1);
 
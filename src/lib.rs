@@ -1,9 +1,12 @@
 #![doc = include_str!("lib.md")]
 
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse_macro_input, Data, DeriveInput, Error, Expr, Fields
-          , Type, Variant};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::ext::IdentExt;
+use syn::meta::ParseNestedMeta;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Error, Expr, ExprLit, Fields
+          , Lit, LitStr, Meta, Token, Type, Variant};
 
 /// Derives the `UnitEnum` trait for an enum.
 ///
@@ -11,17 +14,118 @@ use syn::{parse_macro_input, Data, DeriveInput, Error, Expr, Fields
 /// that can hold arbitrary discriminant values.
 ///
 /// # Attributes
-/// - `#[repr(type)]`: Optional for regular enums, defaults to i32. Required when using an "other" variant.
+/// - `#[repr(type)]`: Optional, defaults to i32. Required by the compiler to give explicit discriminants to an
+///   enum with an "other" variant.
 /// - `#[unit_enum(other)]`: Marks a variant as the catch-all for undefined discriminant values.
 ///   The type of this variant must match the repr type.
+/// - `#[unit_enum(other(listed = ...))]`: Like `other`, but also counts the variant in `len()` and yields it
+///   with the given payload from `values()` and `from_ordinal()`, and its name from `iter_names()`.
+/// - `#[unit_enum(fallback)]`: Marks a unit variant as the result of `from_discriminant()` for undefined
+///   discriminant values, which are not kept. Cannot be combined with an "other" variant.
+/// - `#[unit_enum(code = "...")]`: Gives a unit variant a code distinct from its name, returned by `code()` and
+///   looked up by `from_code()`. Either every unit variant has a code or none does.
+/// - `#[unit_enum(display = "...")]`: Gives a unit variant a display text, returned by `display_str()` and printed by
+///   a generated `Display` impl, while `name()` keeps the identifier. Variants without one display their name.
+/// - `#[unit_enum(ordinal = n)]`: Pins the ordinal of a unit variant, independently of where it is declared. Either
+///   every unit variant has an ordinal or none does, and they must run from 0 without gaps or duplicates.
+/// - `#[unit_enum(ordinal_by = "declaration" | "discriminant")]`: On the enum, selects whether ordinals follow the
+///   declaration order or the order of the discriminants, which must then be integer or byte literals, or implicit.
+///   Defaults to `"declaration"`.
+/// - `#[unit_enum(assoc(name: Type = default, ...))]`: On the enum, declares typed constants with an optional
+///   default, each read by a `const fn` accessor of the same name. Set them with `#[unit_enum(assoc(name = value))]`
+///   on unit variants. The "other" variant takes the defaults.
+/// - `#[unit_enum(aliases(Alias = Variant, ...))]`: On the enum, declares alternative names for unit variants, such as
+///   legacy names sharing a code. Each alias is an associated constant holding its variant, accepted by `from_name()`
+///   and listed in `ALIASES`.
+/// - `#[unit_enum(reserved = a..=b)]`: On the enum, reserves a range or a single value of discriminants, which no
+///   unit variant may take, and generates `is_reserved()`. Repeat it to reserve several.
+/// - `#[unit_enum(field(offset = n, width = n, word = u32))]`: On the enum, places the discriminant in a bitfield of
+///   a register word, generating `from_register()` and `insert_into_register()`. `word` defaults to `u32`, and every
+///   unit variant's discriminant must fit in the field.
+/// - `#[unit_enum(transitions(From -> To, From -> [To, ...], ...))]`: On the enum, declares the allowed
+///   transitions between unit variants, generating `can_transition_to()`, `successors()` and `transition_to()`.
+/// - `#[unit_enum(random_weight = name)]`: On the enum, with the `rand` feature, implies `rand` and generates
+///   `random_weighted(&mut rng)` sampling unit variants in proportion to the integer `assoc` constant `name`.
+/// - `#[unit_enum(bytemuck, schemars, ...)]`: On the enum, turns on the integrations with other crates, each allowed
+///   by the cargo feature of unit-enum of the same name, with `-` in place of `_`. Enabling a feature alone adds
+///   nothing to an enum, since Cargo unifies features across the whole build.
+/// - `#[unit_enum(serde)]` or `#[unit_enum(serde = "name" | "discriminant" | "auto" | "ordinal")]`: On the enum,
+///   turns on the `serde` integration and selects how it represents variants. `"auto"` uses names in human-readable
+///   formats and discriminants in the others. `"ordinal"` uses the ordinal in the smallest unsigned type holding
+///   it, and cannot be combined with an "other" variant. Defaults to `"name"`.
+/// - `#[unit_enum(deserialize_any)]`: On the enum, makes the `serde` integration deserialize both variant names and
+///   discriminants in human-readable formats, whichever of them it serializes.
+/// - `#[unit_enum(serde_rename)]`: On the enum, makes `name()` and `from_name()` honor
+///   `#[serde(rename = "...")]` on variants and `#[serde(rename_all = "...")]` on the enum.
+/// - `#[unit_enum(strum)]`: On the enum, makes `name()` and `from_name()` honor `#[strum(to_string = "...")]` and
+///   `#[strum(serialize = "...")]` on variants and `#[strum(serialize_all = "...")]` on the enum, like strum's
+///   `Display` and `EnumString` derives.
+/// - `#[unit_enum(arbitrary_other)]`: On the enum, lets the test-data integrations (`arbitrary`,
+///   `proptest`, `quickcheck`) also generate "other" values from arbitrary discriminants.
+/// - `#[unit_enum(sql = "name" | "discriminant")]`: On the enum, selects whether the database integrations
+///   (`sqlx`, `diesel`, `postgres_types`) store the variant name in a text column or the discriminant in an
+///   integer column. Defaults to `"name"`.
+/// - `#[unit_enum(napi)]` or `#[unit_enum(napi = "name" | "discriminant")]`: On the enum, turns on the `napi`
+///   integration and selects whether it converts variants to JavaScript strings or numbers. Defaults to
+///   `"discriminant"`.
+/// - `#[unit_enum(extern_c)]` or `#[unit_enum(extern_c(prefix = "...", sentinel = ...))]`: On the enum,
+///   exports `extern "C"` functions converting between discriminants and names. The prefix defaults to
+///   the enum name in snake case and the sentinel for unknown input to the maximum of the repr type. An enum with an
+///   "other" variant must give the sentinel.
+/// - `#[unit_enum(as_ref)]`: On the enum, implements `AsRef<str>` returning the variant name.
+/// - `#[unit_enum(into_str)]`: On the enum, implements `From<Enum> for &'static str` returning the variant name.
+/// - `#[unit_enum(from_str)]`: On the enum, implements `FromStr` and `TryFrom<&str>` on top of `from_name()`,
+///   failing with a generated `Parse{Enum}Error` type, and adds `from_env()` reading an environment variable.
+/// - `#[unit_enum(parse(prefix, trim, separators, numeric))]`: On the enum, implies `from_str` and tunes it. `prefix`
+///   accepts unambiguous prefixes of variant names, `trim` ignores surrounding whitespace, `separators` treats `-`,
+///   `_` and spaces as the same character, and `numeric` accepts discriminants in decimal or with a `0x`, `0o` or
+///   `0b` prefix.
+/// - `#[unit_enum(eq_str)]`: On the enum, implements `PartialEq` against `str` and `&str`, in both directions,
+///   comparing the variant name.
+/// - `#[unit_enum(std_derives)]`: On the enum, implements `Clone`, `Copy`, `PartialEq`, `Eq` and `Hash` like the
+///   standard derives would.
+/// - `#[unit_enum(display_with = "path::to::fn")]`: On the enum, implements `Display` by calling the given
+///   function with the variant and the formatter, as `fn(&Enum, &mut fmt::Formatter<'_>) -> fmt::Result`.
+/// - `#[unit_enum(for_each_variant)]`: On the enum, defines a `for_each_{enum}_variant!` macro that invokes a
+///   callback macro once per unit variant with its identifier, name, ordinal and discriminant.
+/// - `#[unit_enum(generate_tests)]`: On the enum, emits `#[cfg(test)]` tests next to it checking that names, ordinals
+///   and discriminants round-trip and that undefined discriminants map to the "other" variant.
+/// - `#[unit_enum(kani)]`: On the enum, emits `#[cfg(kani)]` Kani proof harnesses checking that names, ordinals and
+///   discriminants round-trip for every input, and that undefined discriminants are rejected or map to the "other"
+///   or fallback variant.
+/// - `#[unit_enum(fuzz_helpers)]`: On the enum, generates `fuzz_*` associated functions taking `&[u8]`, ready to call
+///   from a `cargo fuzz` target, that check the discriminant and name conversions against each other.
+/// - `#[unit_enum(is_variant)]`: On the enum, generates an `is_{variant}()` predicate for each variant, with the
+///   variant name in snake case.
+/// - `#[unit_enum(known)]`: On an enum with an "other" variant, generates a `{Enum}Known` enum with only the unit
+///   variants, `From<{Enum}Known> for {Enum}` and `as_known()`.
+/// - `#[unit_enum(counts)]`: On the enum, generates a `{Enum}Counts` type tallying values by variant, with
+///   `record()`, `get()`, `total()`, `iter()`, `merge()` and a count getter per variant, named in snake case.
+/// - `#[unit_enum(maps_to = path::Enum)]` or `#[unit_enum(maps_to(path::Enum, by = "name" | "discriminant"))]`:
+///   On the enum, implements conversions to and from another enum, matching variants by identifier or, for another
+///   `UnitEnum` with the same repr, by discriminant. Variants without a counterpart fail to compile. Can be repeated.
+/// - `#[unit_enum(info = path::module)]`: On the enum, generates `info()` returning a static `module::EnumInfo`
+///   with the enum name, the repr name and a `module::VariantInfo` with the name, discriminant as `i128` and
+///   ordinal of each unit variant. Both structs are defined in the calling crate.
+/// - `#[unit_enum(any_trait = path::Trait)]`: On the enum, implements a dyn-compatible trait defined in the calling
+///   crate, with `name()`, `ordinal()`, `discriminant_i128()`, `variant_names()` and `set_ordinal()`, so that enums
+///   of different types can be handled as trait objects.
+/// - `#[unit_enum(schema)]`: On the enum, generates `schema_json()` returning a JSON description of the enum with its
+///   name, its repr, the name and discriminant of each unit variant and the name of the "other" variant.
+/// - `#[unit_enum(register = path::Entry)]`: On the enum, with the `inventory` feature, submits
+///   `Entry::new(type_path, variants)` to an `inventory` registry, where `variants` holds the name and the
+///   discriminant as `i128` of each unit variant.
+/// - `#[unit_enum(discriminant_consts)]` or `#[unit_enum(discriminant_consts(module = "..."))]`: On the enum,
+///   defines a module with a constant holding the discriminant of each unit variant, named in screaming snake
+///   case. The module is named `{enum}_discriminants` in snake case unless given. Discriminants must be integer
+///   literals.
 ///
 /// # Requirements
 /// - The enum must contain only unit variants, except for one optional "other" variant
 /// - The "other" variant, if present, must:
 ///   - Be marked with `#[unit_enum(other)]`
-///   - Have exactly one unnamed field matching the repr type
+///   - Have exactly one unnamed field matching the repr type, or `i32` without a `#[repr(type)]` attribute
 ///   - Be the only variant with the "other" attribute
-///   - Have a matching `#[repr(type)]` attribute
 ///
 /// # Examples
 ///
@@ -48,7 +152,7 @@ use syn::{parse_macro_input, Data, DeriveInput, Error, Expr, Fields
 /// }
 /// ```
 ///
-/// Usage with an "other" variant (repr required):
+/// Usage with an "other" variant:
 /// ```rust
 /// # use unit_enum::UnitEnum;
 /// #[derive(UnitEnum)]
@@ -60,26 +164,649 @@ use syn::{parse_macro_input, Data, DeriveInput, Error, Expr, Fields
 ///     Unknown(u16),  // type must match repr
 /// }
 /// ```
-#[proc_macro_derive(UnitEnum, attributes(unit_enum))]
+///
+/// Without a repr, the "other" variant holds an `i32` and discriminants are implicit:
+/// ```rust
+/// # use unit_enum::UnitEnum;
+/// #[derive(UnitEnum)]
+/// enum Level {
+///     Debug, // 0
+///     Info,  // 1
+///     #[unit_enum(other)]
+///     Custom(i32),
+/// }
+/// ```
+#[proc_macro_derive(UnitEnum, attributes(unit_enum, serde, strum))]
 pub fn unit_enum_derive(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
 
     match validate_and_process(&ast) {
-        Ok((discriminant_type, unit_variants, other_variant)) => {
-            impl_unit_enum(&ast, &discriminant_type, &unit_variants, other_variant)
+        Ok(validation) => impl_unit_enum(&ast, validation),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Defines an enum and derives `UnitEnum` for it, with shorthands for large code tables.
+///
+/// The input is a regular enum declaration, where a unit variant's discriminant may also be:
+/// - A range of integer literals, `Reserved = 0x10..=0x1F` or `Reserved = 0x10..0x20`, defining one variant
+///   per value
+/// - A list of integer literals, `Pin = [0, 2, 4]`, defining one variant per value
+///
+/// Expanded variants are named after the value, in hexadecimal when the first literal is written in hexadecimal
+/// (zero-padded like the literals, `Reserved10` to `Reserved1F`) and in decimal otherwise (`Pin0`, `Pin2`, `Pin4`).
+/// Attributes of the entry, such as doc comments, are copied to every variant, so attributes giving a variant a
+/// value of its own, like `#[unit_enum(code = "...")]` or `#[serde(rename = "...")]`, are rejected. An entry
+/// expands to at most 4096 variants.
+///
+/// # Examples
+///
+/// ```rust
+/// use unit_enum::{unit_enum, UnitEnum};
+///
+/// unit_enum! {
+///     #[derive(Debug, PartialEq)]
+///     #[repr(u8)]
+///     enum Register {
+///         Control = 0x00,
+///         Status,
+///         Reserved = 0x02..=0x0F,
+///         Channel = [0x10, 0x20],
+///     }
+/// }
+///
+/// assert_eq!(Register::len(), 18);
+/// assert_eq!(Register::Reserved0F.discriminant(), 0x0F);
+/// assert_eq!(Register::from_name("Channel20"), Some(Register::Channel20));
+/// ```
+#[proc_macro]
+pub fn unit_enum(input: TokenStream) -> TokenStream {
+    let mut ast = parse_macro_input!(input as DeriveInput);
+
+    match expand_variant_shorthands(&mut ast) {
+        Ok(()) => quote! {
+            #[derive(::unit_enum::UnitEnum)]
+            #ast
+        }.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// The most variants a single range or list in `unit_enum!` expands to.
+const MAX_SHORTHAND_VARIANTS: u128 = 4096;
+
+fn expand_variant_shorthands(ast: &mut DeriveInput) -> Result<(), Error> {
+    let data_enum = match &mut ast.data {
+        Data::Enum(data_enum) => data_enum,
+        _ => return Err(Error::new_spanned(&ast.ident, "unit_enum! can only define enums")),
+    };
+
+    let mut variants = syn::punctuated::Punctuated::<Variant, Token![,]>::new();
+    for variant in std::mem::take(&mut data_enum.variants) {
+        let values = match variant.discriminant.as_ref().map(|(_, expr)| expr) {
+            Some(Expr::Range(range)) => {
+                let (start, end) = range.start.as_deref().zip(range.end.as_deref())
+                    .ok_or_else(|| Error::new_spanned(range, "Variant ranges must have a start and an end"))?;
+                let start = shorthand_literal(start)?;
+                let end = shorthand_literal(end)?;
+                let (start_value, end_value) = (start.base10_parse::<u128>()?, end.base10_parse::<u128>()?);
+                // Checked before collecting, as a range like `0..=u128::MAX` would never finish
+                let end_value = match range.limits {
+                    syn::RangeLimits::Closed(_) => end_value,
+                    syn::RangeLimits::HalfOpen(_) if end_value > start_value => end_value - 1,
+                    syn::RangeLimits::HalfOpen(_) => return Err(Error::new_spanned(range, "Variant range is empty")),
+                };
+                if end_value < start_value {
+                    return Err(Error::new_spanned(range, "Variant range is empty"));
+                }
+                if end_value - start_value >= MAX_SHORTHAND_VARIANTS {
+                    return Err(Error::new_spanned(range, format!(
+                        "Variant range expands to more than {} variants", MAX_SHORTHAND_VARIANTS)));
+                }
+                Some((vec![start, end], (start_value..=end_value).collect::<Vec<_>>()))
+            }
+            Some(Expr::Array(array)) => {
+                let literals = array.elems.iter().map(shorthand_literal).collect::<Result<Vec<_>, _>>()?;
+                if literals.is_empty() {
+                    return Err(Error::new_spanned(array, "Variant list is empty"));
+                }
+                if literals.len() as u128 > MAX_SHORTHAND_VARIANTS {
+                    return Err(Error::new_spanned(array, format!(
+                        "Variant list expands to more than {} variants", MAX_SHORTHAND_VARIANTS)));
+                }
+                let values = literals.iter().map(|literal| literal.base10_parse::<u128>()).collect::<Result<_, _>>()?;
+                Some((literals, values))
+            }
+            _ => None,
+        };
+
+        let Some((literals, values)) = values else {
+            variants.push(variant);
+            continue;
+        };
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(&variant.ident, "Only unit variants can use ranges or lists"));
+        }
+        reject_shorthand_values(&variant)?;
+
+        let hex = literals[0].to_string().to_ascii_lowercase().starts_with("0x");
+        // Keep the zero-padding of the written literals, so that `0x01..=0x0F` names `01` to `0F`
+        let width = literals.iter()
+            .map(|literal| {
+                let text = literal.to_string();
+                text.trim_end_matches(literal.suffix()).chars().filter(|c| *c != '_').count().saturating_sub(2)
+            })
+            .max()
+            .unwrap_or(0);
+        for value in values {
+            let suffix = if hex { format!("{value:0width$X}") } else { value.to_string() };
+            let literal = proc_macro2::Literal::u128_unsuffixed(value);
+            variants.push(Variant {
+                ident: format_ident!("{}{}", variant.ident, suffix, span = variant.ident.span()),
+                discriminant: Some((Default::default(), syn::parse_quote!(#literal))),
+                ..variant.clone()
+            });
         }
+    }
+    data_enum.variants = variants;
+
+    Ok(())
+}
+
+/// Rejects attributes that give a variant a value of its own, such as a code or a name, on a range or a list,
+/// since every variant expanded from it would get the same value.
+fn reject_shorthand_values(variant: &Variant) -> Result<(), Error> {
+    for attr in variant.attrs.iter().filter(|attr| ["unit_enum", "serde", "strum"].iter().any(|path| attr.path().is_ident(path))) {
+        attr.parse_nested_meta(|meta| {
+            if meta.input.peek(Token![=]) || meta.input.peek(syn::token::Paren) {
+                let key = meta.path.to_token_stream().to_string();
+                Err(meta.error(format!(
+                    "`{}` can't be given to a range or list of variants, as every variant would get the same value", key)))
+            } else {
+                Ok(())
+            }
+        })?;
+    }
+    Ok(())
+}
+
+fn shorthand_literal(expr: &Expr) -> Result<&syn::LitInt, Error> {
+    match expr {
+        Expr::Lit(ExprLit { lit: Lit::Int(literal), .. }) => Ok(literal),
+        _ => Err(Error::new_spanned(expr, "Variant ranges and lists must contain integer literals")),
+    }
+}
+
+/// Defines an enum from a data file of name and value pairs and derives `UnitEnum` for it.
+///
+/// The file path is relative to the crate's `Cargo.toml`, and the crate is rebuilt when the file changes. Each
+/// line holds a variant name, an integer discriminant and an optional description that becomes the variant's
+/// doc comment, separated by commas. Blank lines and lines starting with `#` are ignored, as is a leading
+/// `name,value` or `name,value,description` header row. Further variants, like an "other" variant, can be added in
+/// braces.
+///
+/// # Examples
+///
+/// With a `codes.csv` file such as:
+///
+/// ```text
+/// name,value,description
+/// Ok,0,Success
+/// NotFound,0x10,The entry does not exist
+/// ```
+///
+/// ```ignore
+/// use unit_enum::unit_enum_from_file;
+///
+/// unit_enum_from_file! {
+///     #[derive(Debug, PartialEq)]
+///     #[repr(u16)]
+///     pub enum Code = "codes.csv" {
+///         #[unit_enum(other)]
+///         Unknown(u16),
+///     }
+/// }
+///
+/// assert_eq!(Code::from_discriminant(0x10), Code::NotFound);
+/// ```
+#[proc_macro]
+pub fn unit_enum_from_file(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as EnumFromFile);
+
+    match enum_from_file(input) {
+        Ok(tokens) => tokens.into(),
         Err(e) => e.to_compile_error().into(),
     }
 }
 
+/// Input of `unit_enum_from_file!`: an enum header naming the data file, with optional extra variants.
+struct EnumFromFile {
+    attrs: Vec<Attribute>,
+    vis: syn::Visibility,
+    ident: syn::Ident,
+    path: LitStr,
+    variants: syn::punctuated::Punctuated<Variant, Token![,]>,
+}
+
+impl syn::parse::Parse for EnumFromFile {
+    fn parse(input: syn::parse::ParseStream) -> Result<Self, Error> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![enum]>()?;
+        let ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let path = input.parse()?;
+        let variants = if input.peek(syn::token::Brace) {
+            let content;
+            syn::braced!(content in input);
+            content.parse_terminated(Variant::parse, Token![,])?
+        } else {
+            input.parse::<Token![;]>()?;
+            Default::default()
+        };
+
+        Ok(EnumFromFile { attrs, vis, ident, path, variants })
+    }
+}
+
+fn enum_from_file(input: EnumFromFile) -> Result<proc_macro2::TokenStream, Error> {
+    let EnumFromFile { attrs, vis, ident, path, variants: extra_variants } = input;
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let full_path = std::path::Path::new(&manifest_dir).join(path.value());
+    let contents = std::fs::read_to_string(&full_path)
+        .map_err(|e| Error::new_spanned(&path, format!("Failed to read {}: {}", full_path.display(), e)))?;
+
+    let mut variants: Vec<Variant> = Vec::new();
+    let mut first_row = true;
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let error = |message: String| Error::new_spanned(&path, format!("{}:{}: {}", path.value(), index + 1, message));
+
+        let mut columns = line.splitn(3, ',').map(str::trim);
+        let variant_name = columns.next().unwrap_or_default();
+        let value = columns.next().unwrap_or_default();
+        let description = columns.next().map(|text| text.trim_matches('"')).filter(|text| !text.is_empty());
+
+        // Only a row naming the columns counts as a header, so a bad value in the first row is still reported
+        let is_header = variant_name.eq_ignore_ascii_case("name") && value.eq_ignore_ascii_case("value")
+            && description.is_none_or(|text| text.eq_ignore_ascii_case("description"));
+        if std::mem::take(&mut first_row) && is_header {
+            continue;
+        }
+        let discriminant = syn::parse_str::<Expr>(value).ok().filter(|expr| match expr {
+            Expr::Unary(syn::ExprUnary { op: syn::UnOp::Neg(_), expr, .. }) => shorthand_literal(expr).is_ok(),
+            expr => shorthand_literal(expr).is_ok(),
+        });
+        let Some(discriminant) = discriminant else {
+            return Err(error(format!("`{}` is not an integer literal", value)));
+        };
+
+        let variant_ident: syn::Ident = syn::parse_str(variant_name)
+            .map_err(|_| error(format!("`{}` is not a valid variant name", variant_name)))?;
+        let doc = description.map(|text| quote! { #[doc = #text] });
+        variants.push(syn::parse_quote! { #doc #variant_ident = #discriminant });
+    }
+
+    let tracked_path = full_path.display().to_string();
+    Ok(quote! {
+        #[derive(::unit_enum::UnitEnum)]
+        #(#attrs)*
+        #vis enum #ident {
+            #(#variants,)*
+            #extra_variants
+        }
+
+        const _: &[u8] = include_bytes!(#tracked_path);
+    })
+}
+
 struct ValidationResult<'a> {
+    discriminant_type: Type,
+    has_explicit_repr: bool,
+    attributes: ContainerAttributes,
     unit_variants: Vec<&'a Variant>,
+    unit_names: Vec<LitStr>,
+    unit_aliases: Vec<Vec<LitStr>>,
     other_variant: Option<(&'a Variant, Type)>,
+    other_name: Option<LitStr>,
+    other_listed: Option<Expr>,
+    fallback_variant: Option<&'a Variant>,
+    unit_codes: Vec<Option<LitStr>>,
+    unit_assoc: Vec<Vec<(syn::Ident, Expr)>>,
+    unit_displays: Vec<Option<LitStr>>,
+    unit_discriminants: Vec<Expr>,
+}
+
+/// Options set with `#[unit_enum(...)]` on the enum itself.
+#[derive(Default)]
+struct ContainerAttributes {
+    serde_mode: SerdeMode,
+    deserialize_any: bool,
+    serde_rename: bool,
+    rename_all: Option<RenameRule>,
+    strum: bool,
+    strum_case: Option<StrumCase>,
+    arbitrary_other: bool,
+    sql_mode: SqlMode,
+    napi_mode: NapiMode,
+    extern_c: Option<ExternC>,
+    as_ref: bool,
+    into_str: bool,
+    from_str: bool,
+    parse: ParseOptions,
+    eq_str: bool,
+    std_derives: bool,
+    display_with: Option<syn::Path>,
+    for_each_variant: bool,
+    generate_tests: bool,
+    kani: bool,
+    fuzz_helpers: bool,
+    is_variant: bool,
+    discriminant_consts: Option<DiscriminantConsts>,
+    known: bool,
+    counts: bool,
+    maps_to: Vec<MapsTo>,
+    register: Option<syn::Path>,
+    info: Option<syn::Path>,
+    any_trait: Option<syn::Path>,
+    schema: bool,
+    assoc: Vec<AssocField>,
+    transitions: Option<Vec<Transition>>,
+    random_weight: Option<syn::Ident>,
+    ordinal_by: OrdinalBy,
+    aliases: Vec<(syn::Ident, syn::Ident)>,
+    reserved: Vec<Expr>,
+    field: Option<RegisterField>,
+    integrations: Vec<&'static str>,
+}
+
+impl ContainerAttributes {
+    /// Whether the enum turned on the integration with `#[unit_enum(key)]`.
+    fn uses(&self, key: &str) -> bool {
+        self.integrations.contains(&key)
+    }
+}
+
+/// An edge list of `#[unit_enum(transitions(From -> To, From -> [To, ...]))]`.
+struct Transition {
+    from: syn::Ident,
+    to: Vec<syn::Ident>,
+}
+
+impl syn::parse::Parse for Transition {
+    fn parse(input: syn::parse::ParseStream) -> Result<Self, Error> {
+        let from = input.parse()?;
+        input.parse::<Token![->]>()?;
+        let to = if input.peek(syn::token::Bracket) {
+            let content;
+            syn::bracketed!(content in input);
+            content.parse_terminated(syn::Ident::parse, Token![,])?.into_iter().collect()
+        } else {
+            vec![input.parse()?]
+        };
+        Ok(Transition { from, to })
+    }
+}
+
+/// A constant declared with `#[unit_enum(assoc(name: Type = default, ...))]` and set per variant.
+struct AssocField {
+    name: syn::Ident,
+    ty: Type,
+    default: Option<Expr>,
+}
+
+impl syn::parse::Parse for AssocField {
+    fn parse(input: syn::parse::ParseStream) -> Result<Self, Error> {
+        let name = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let ty = input.parse()?;
+        let default = input.parse::<Option<Token![=]>>()?.map(|_| input.parse()).transpose()?;
+        Ok(AssocField { name, ty, default })
+    }
+}
+
+/// A target of `#[unit_enum(maps_to = ...)]` or `#[unit_enum(maps_to(..., by = "..."))]`.
+struct MapsTo {
+    target: syn::Path,
+    by_discriminant: bool,
+}
+
+/// A bitfield of a register word declared with `#[unit_enum(field(offset = ..., width = ..., word = ...))]`.
+struct RegisterField {
+    offset: u32,
+    width: u32,
+    width_span: proc_macro2::Span,
+    word: Type,
+}
+
+/// Options of `#[unit_enum(discriminant_consts(...))]`.
+#[derive(Default)]
+struct DiscriminantConsts {
+    module: Option<syn::Ident>,
+}
+
+/// Options of `#[unit_enum(parse(...))]`, applied by the generated `FromStr`.
+#[derive(Default)]
+struct ParseOptions {
+    prefix: bool,
+    trim: bool,
+    separators: bool,
+    numeric: bool,
+}
+
+/// Options of `#[unit_enum(extern_c(...))]`.
+#[derive(Default)]
+struct ExternC {
+    prefix: Option<syn::Ident>,
+    sentinel: Option<Expr>,
+}
+
+/// Case conversion applied by `#[serde(rename_all = "...")]`, mirroring serde's own rules.
+#[derive(Clone, Copy)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn from_literal(rule: &LitStr) -> Result<Self, Error> {
+        match rule.value().as_str() {
+            "lowercase" => Ok(RenameRule::Lower),
+            "UPPERCASE" => Ok(RenameRule::Upper),
+            "PascalCase" => Ok(RenameRule::Pascal),
+            "camelCase" => Ok(RenameRule::Camel),
+            "snake_case" => Ok(RenameRule::Snake),
+            "SCREAMING_SNAKE_CASE" => Ok(RenameRule::ScreamingSnake),
+            "kebab-case" => Ok(RenameRule::Kebab),
+            "SCREAMING-KEBAB-CASE" => Ok(RenameRule::ScreamingKebab),
+            _ => Err(Error::new_spanned(rule, "Unknown rename rule")),
+        }
+    }
+
+    /// Applies the rule to a PascalCase variant name.
+    fn apply(self, variant: &str) -> String {
+        match self {
+            RenameRule::Lower => variant.to_ascii_lowercase(),
+            RenameRule::Upper => variant.to_ascii_uppercase(),
+            RenameRule::Pascal => variant.to_owned(),
+            RenameRule::Camel => {
+                let mut chars = variant.chars();
+                chars.next().map_or_else(String::new, |first| first.to_ascii_lowercase().to_string() + chars.as_str())
+            }
+            RenameRule::Snake => {
+                let mut snake = String::new();
+                for (i, ch) in variant.char_indices() {
+                    if i > 0 && ch.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(ch.to_ascii_lowercase());
+                }
+                snake
+            }
+            RenameRule::ScreamingSnake => RenameRule::Snake.apply(variant).to_ascii_uppercase(),
+            RenameRule::Kebab => RenameRule::Snake.apply(variant).replace('_', "-"),
+            RenameRule::ScreamingKebab => RenameRule::ScreamingSnake.apply(variant).replace('_', "-"),
+        }
+    }
+}
+
+/// Case conversion applied by `#[strum(serialize_all = "...")]`, mirroring strum's rules. Unlike serde's,
+/// they split words the way the `heck` crate does, so acronyms stay together (`HTTPCode` -> `http_code`), which
+/// also makes them the rules for naming generated items.
+#[derive(Clone, Copy)]
+enum StrumCase {
+    Pascal,
+    Camel,
+    Mixed,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+    Title,
+    Train,
+    Lower,
+    Upper,
+}
+
+impl StrumCase {
+    fn from_literal(style: &LitStr) -> Result<Self, Error> {
+        match style.value().as_str() {
+            "PascalCase" | "camel_case" => Ok(StrumCase::Pascal),
+            "camelCase" => Ok(StrumCase::Camel),
+            "mixed_case" => Ok(StrumCase::Mixed),
+            "snake_case" | "snek_case" => Ok(StrumCase::Snake),
+            "SCREAMING_SNAKE_CASE" | "shouty_snake_case" | "shouty_snek_case" => Ok(StrumCase::ScreamingSnake),
+            "kebab-case" | "kebab_case" => Ok(StrumCase::Kebab),
+            "SCREAMING-KEBAB-CASE" => Ok(StrumCase::ScreamingKebab),
+            "title_case" => Ok(StrumCase::Title),
+            "Train-Case" => Ok(StrumCase::Train),
+            "lowercase" => Ok(StrumCase::Lower),
+            "UPPERCASE" => Ok(StrumCase::Upper),
+            _ => Err(Error::new_spanned(style, "Unknown strum case style")),
+        }
+    }
+
+    /// Applies the style to a variant name.
+    fn apply(self, variant: &str) -> String {
+        let words = heck_words(variant);
+        let capitalize = |word: &str| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| first.to_uppercase().chain(chars.as_str().to_lowercase().chars()).collect())
+        };
+        let join = |convert: &dyn Fn(&str) -> String, separator: &str| {
+            words.iter().map(|word| convert(word)).collect::<Vec<_>>().join(separator)
+        };
+
+        match self {
+            StrumCase::Pascal => join(&capitalize, ""),
+            StrumCase::Camel => {
+                let pascal = StrumCase::Pascal.apply(variant);
+                let mut chars = pascal.chars();
+                chars.next().map_or_else(String::new, |first| first.to_lowercase().chain(chars).collect())
+            }
+            StrumCase::Mixed => words.iter().enumerate()
+                .map(|(index, word)| if index == 0 { word.to_lowercase() } else { capitalize(word) })
+                .collect(),
+            StrumCase::Snake => join(&str::to_lowercase, "_"),
+            StrumCase::ScreamingSnake => join(&str::to_uppercase, "_"),
+            StrumCase::Kebab => join(&str::to_lowercase, "-"),
+            StrumCase::ScreamingKebab => join(&str::to_uppercase, "-"),
+            StrumCase::Title => join(&capitalize, " "),
+            StrumCase::Train => join(&capitalize, "-"),
+            StrumCase::Lower => variant.to_lowercase(),
+            StrumCase::Upper => variant.to_uppercase(),
+        }
+    }
+}
+
+/// Splits a name into words like the `heck` crate: at underscores, before an uppercase letter following a
+/// lowercase one, and before the last letter of an uppercase run followed by a lowercase one.
+fn heck_words(name: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    for word in name.split(|ch: char| !ch.is_alphanumeric()) {
+        let mut chars = word.char_indices().peekable();
+        let mut start = 0;
+        // Whether the last cased character of the current word was uppercase
+        let mut upper = None;
+        while let Some((i, ch)) = chars.next() {
+            let Some(&(next_i, next)) = chars.peek() else {
+                words.push(&word[start..]);
+                break;
+            };
+            let next_upper = if ch.is_lowercase() {
+                Some(false)
+            } else if ch.is_uppercase() {
+                Some(true)
+            } else {
+                upper
+            };
+            if next_upper == Some(false) && next.is_uppercase() {
+                words.push(&word[start..next_i]);
+                start = next_i;
+                upper = None;
+            } else if upper == Some(true) && ch.is_uppercase() && next.is_lowercase() {
+                words.push(&word[start..i]);
+                start = i;
+                upper = None;
+            } else {
+                upper = next_upper;
+            }
+        }
+    }
+    words
+}
+
+/// How the `serde` integration represents variants on the wire.
+#[derive(Clone, Copy, Default, PartialEq)]
+enum SerdeMode {
+    #[default]
+    Name,
+    Discriminant,
+    /// Names for human-readable formats, discriminants for the others.
+    Auto,
+    /// The ordinal in the smallest unsigned type holding it.
+    Ordinal,
+}
+
+/// How the database integrations (`sqlx`, `diesel`, `postgres_types`) store variants in a column.
+#[derive(Clone, Copy, Default, PartialEq)]
+enum SqlMode {
+    #[default]
+    Name,
+    Discriminant,
+}
+
+/// How the `napi` integration represents variants in JavaScript.
+#[derive(Clone, Copy, Default, PartialEq)]
+enum NapiMode {
+    Name,
+    /// Numbers, like napi-rs' own `#[napi]` enums
+    #[default]
+    Discriminant,
+}
+
+/// The order that ordinals follow.
+#[derive(Clone, Copy, Default, PartialEq)]
+enum OrdinalBy {
+    #[default]
+    Declaration,
+    Discriminant,
 }
 
-fn validate_and_process(ast: &DeriveInput) -> Result<(Type, Vec<&Variant>, Option<(&Variant, Type)>), Error> {
+fn validate_and_process(ast: &DeriveInput) -> Result<ValidationResult<'_>, Error> {
     // Get discriminant type from #[repr] attribute
     let discriminant_type = get_discriminant_type(ast)?;
+    let has_explicit_repr = ast.attrs.iter().any(|attr| attr.path().is_ident("repr"));
+    let attributes = parse_container_attributes(ast)?;
 
     let data_enum = match &ast.data {
         Data::Enum(data_enum) => data_enum,
@@ -87,27 +814,66 @@ fn validate_and_process(ast: &DeriveInput) -> Result<(Type, Vec<&Variant>, Optio
     };
 
     let mut validation = ValidationResult {
+        discriminant_type,
+        has_explicit_repr,
+        attributes,
         unit_variants: Vec::new(),
+        unit_names: Vec::new(),
+        unit_aliases: Vec::new(),
         other_variant: None,
+        other_name: None,
+        other_listed: None,
+        fallback_variant: None,
+        unit_codes: Vec::new(),
+        unit_assoc: Vec::new(),
+        unit_displays: Vec::new(),
+        unit_discriminants: Vec::new(),
     };
 
+    let mut unit_ordinals = Vec::new();
+
     // Validate each variant
     for variant in &data_enum.variants {
         match &variant.fields {
             Fields::Unit => {
-                if has_unit_enum_attr(variant) {
-                    return Err(Error::new_spanned(variant,
-                                                  "Unit variants cannot have #[unit_enum] attributes"));
+                let variant_attributes = parse_variant_attributes(variant)?;
+                if variant_attributes.fallback {
+                    if validation.fallback_variant.is_some() {
+                        return Err(Error::new_spanned(variant,
+                                                      "Multiple #[unit_enum(fallback)] variants found. Only one is allowed"));
+                    }
+                    validation.fallback_variant = Some(variant);
                 }
+                validation.unit_codes.push(variant_attributes.code);
+                validation.unit_assoc.push(variant_attributes.assoc);
+                validation.unit_displays.push(variant_attributes.display);
+                unit_ordinals.push(variant_attributes.ordinal);
+                let variant_name = resolve_variant_name(variant, &validation.attributes)?;
+                validation.unit_aliases.push(resolve_variant_aliases(variant, &validation.attributes, &variant_name)?);
                 validation.unit_variants.push(variant);
+                validation.unit_names.push(variant_name);
             }
             Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
-                if has_unit_enum_other_attr(variant) {
+                if has_unit_enum_other_attr(variant)? {
                     if validation.other_variant.is_some() {
                         return Err(Error::new_spanned(variant,
                                                       "Multiple #[unit_enum(other)] variants found. Only one is allowed"));
                     }
-                    validation.other_variant = Some((variant, fields.unnamed[0].ty.clone()));
+                    let other_type = &fields.unnamed[0].ty;
+                    if is_primitive_integer(other_type) && is_primitive_integer(&validation.discriminant_type)
+                        && !widens_losslessly(&validation.discriminant_type, other_type) {
+                        let message = if has_explicit_repr {
+                            format!("The #[unit_enum(other)] field must have the repr type `{}` or an integer type it widens to losslessly",
+                                    validation.discriminant_type.to_token_stream())
+                        } else {
+                            "The #[unit_enum(other)] field must be `i32`, the default repr, a type it widens to losslessly, or match an explicit #[repr]"
+                                .to_string()
+                        };
+                        return Err(Error::new_spanned(other_type, message));
+                    }
+                    validation.other_variant = Some((variant, other_type.clone()));
+                    validation.other_name = Some(resolve_variant_name(variant, &validation.attributes)?);
+                    validation.other_listed = parse_other_listed(variant)?;
                 } else {
                     return Err(Error::new_spanned(variant,
                                                   "Non-unit variant must be marked with #[unit_enum(other)] to be used as the catch-all variant"));
@@ -118,83 +884,1286 @@ fn validate_and_process(ast: &DeriveInput) -> Result<(Type, Vec<&Variant>, Optio
         }
     }
 
-    Ok((discriminant_type, validation.unit_variants, validation.other_variant))
-}
-
-fn get_discriminant_type(ast: &DeriveInput) -> Result<Type, Error> {
-    ast.attrs.iter()
-        .find(|attr| attr.path().is_ident("repr"))
-        .map_or(Ok(syn::parse_quote!(i32)), |attr| {
-            attr.parse_args::<Type>()
-                .map_err(|_| Error::new_spanned(attr, "Invalid repr attribute"))
-        })
-}
+    // Implicit discriminants count from the previous variant in declaration order, so they're resolved before sorting
+    validation.unit_discriminants = compute_discriminants(&validation.unit_variants);
+    if let Some(field) = &validation.attributes.field {
+        // Wider fields would be truncated when read, so that unrelated bit patterns match variants
+        let field_type = encoded_discriminant_type(&validation.other_variant, &validation.discriminant_type);
+        if let Some(bits) = integer_bits(field_type).filter(|&bits| field.width > bits) {
+            return Err(Error::new(field.width_span, format!(
+                "A {}-bit register field is wider than the {}-bit `{}` it is read into", field.width, bits,
+                field_type.to_token_stream())));
+        }
+        for (variant, discriminant) in validation.unit_variants.iter().zip(&validation.unit_discriminants) {
+            // Negative values set the high bits, as they do when the discriminant is written to the register
+            match discriminant_value(discriminant) {
+                Some(value) if (value as u128).checked_shr(field.width).unwrap_or(0) != 0 => {
+                    return Err(Error::new_spanned(variant, format!(
+                        "Discriminant {} of `{}` doesn't fit in the {}-bit register field", value, variant.ident,
+                        field.width)));
+                }
+                _ => {}
+            }
+        }
+    }
+    // Discriminants that can't be evaluated here are still caught by the const assertions
+    for (variant, discriminant) in validation.unit_variants.iter().zip(&validation.unit_discriminants) {
+        if let Some(value) = discriminant_value(discriminant) {
+            if validation.attributes.reserved.iter().any(|reserved| reserved_contains(reserved, value) == Some(true)) {
+                return Err(Error::new_spanned(variant, format!(
+                    "Discriminant {} of `{}` is reserved by #[unit_enum(reserved = ...)]", value, variant.ident)));
+            }
+        }
+    }
+    // is_reserved() is an associated function taking a discriminant, so a predicate can't share its name
+    if validation.attributes.is_variant && !validation.attributes.reserved.is_empty() {
+        let other = validation.other_variant.as_ref().map(|(variant, _)| *variant);
+        if let Some(variant) = validation.unit_variants.iter().copied().chain(other)
+            .find(|variant| StrumCase::Snake.apply(&variant.ident.unraw().to_string()) == "reserved") {
+            return Err(Error::new_spanned(&variant.ident, format!(
+                "`{}` can't be used with #[unit_enum(is_variant)] and #[unit_enum(reserved = ...)], as its predicate \
+                 clashes with `is_reserved()`", variant.ident)));
+        }
+    }
+    if validation.attributes.discriminant_consts.is_some() {
+        if let Some((variant, _)) = validation.unit_variants.iter().zip(&validation.unit_discriminants)
+            .find(|(_, discriminant)| discriminant_value(discriminant).is_none()) {
+            return Err(Error::new_spanned(variant,
+                "discriminant_consts needs integer literal discriminants, as its module can't refer back to the enum"));
+        }
+    }
+    if validation.attributes.ordinal_by == OrdinalBy::Discriminant {
+        let values = validation.unit_variants.iter().zip(&validation.unit_discriminants)
+            .map(|(variant, discriminant)| discriminant_value(discriminant).ok_or_else(|| Error::new_spanned(variant,
+                "ordinal_by = \"discriminant\" needs integer literal discriminants to sort variants by")))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut order: Vec<usize> = (0..values.len()).collect();
+        order.sort_by_key(|&index| values[index]);
+        reorder_unit_variants(&mut validation, &order);
+    }
 
-fn has_unit_enum_attr(variant: &Variant) -> bool {
-    variant.attrs.iter().any(|attr| attr.path().is_ident("unit_enum"))
-}
+    // Pinned ordinals are all or nothing too, and must number the unit variants from zero without gaps
+    if let Some(ordinal) = unit_ordinals.iter().flatten().next() {
+        if validation.attributes.ordinal_by == OrdinalBy::Discriminant {
+            return Err(Error::new_spanned(ordinal,
+                                          "#[unit_enum(ordinal = ...)] cannot be combined with ordinal_by = \"discriminant\""));
+        }
+        let num_variants = validation.unit_variants.len();
+        let mut order = vec![None; num_variants];
+        for (index, (variant, ordinal)) in validation.unit_variants.iter().zip(&unit_ordinals).enumerate() {
+            let ordinal = ordinal.as_ref().ok_or_else(|| Error::new_spanned(variant,
+                "Missing #[unit_enum(ordinal = ...)]. Either every unit variant has an ordinal or none does"))?;
+            let value = ordinal.base10_parse::<usize>()?;
+            match order.get_mut(value) {
+                None => return Err(Error::new_spanned(ordinal, format!(
+                    "Ordinal {} leaves a gap, the {} unit variants must take ordinals 0 to {}",
+                    value, num_variants, num_variants - 1))),
+                Some(Some(_)) => return Err(Error::new_spanned(ordinal, format!("Duplicate ordinal {}", value))),
+                Some(slot) => *slot = Some(index),
+            }
+        }
+        let order: Vec<usize> = order.into_iter().flatten().collect();
+        reorder_unit_variants(&mut validation, &order);
+    }
 
-fn has_unit_enum_other_attr(variant: &Variant) -> bool {
-    variant.attrs.iter().any(|attr| {
-        attr.path().is_ident("unit_enum") &&
-            attr.parse_nested_meta(|meta| {
-                if meta.path.is_ident("other") {
-                    Ok(())
-                } else {
-                    Err(meta.error("Invalid unit_enum attribute"))
+    // Codes are all or nothing, so code() is defined for every unit variant
+    if validation.unit_codes.iter().any(Option::is_some) {
+        let mut seen = std::collections::HashSet::new();
+        for (variant, code) in validation.unit_variants.iter().zip(&validation.unit_codes) {
+            match code {
+                None => return Err(Error::new_spanned(variant,
+                                                      "Missing #[unit_enum(code = \"...\")]. Either every unit variant has a code or none does")),
+                Some(code) if !seen.insert(code.value()) => {
+                    return Err(Error::new_spanned(code, format!("Duplicate code `{}`", code.value())));
                 }
-            }).is_ok()
-    })
-}
+                Some(_) => {}
+            }
+        }
+    }
 
-fn compute_discriminants(variants: &[&Variant]) -> Vec<Expr> {
-    let mut discriminants = Vec::with_capacity(variants.len());
-    let mut last_discriminant: Option<Expr> = None;
+    for (variant, values) in validation.unit_variants.iter().zip(&validation.unit_assoc) {
+        for (name, _) in values {
+            if !validation.attributes.assoc.iter().any(|field| field.name == *name) {
+                return Err(Error::new_spanned(name, format!(
+                    "Unknown assoc constant `{}`. Declare it with #[unit_enum(assoc({}: Type))] on the enum", name, name)));
+            }
+        }
+        for field in validation.attributes.assoc.iter().filter(|field| field.default.is_none()) {
+            if !values.iter().any(|(name, _)| *name == field.name) {
+                return Err(Error::new_spanned(variant,
+                                              format!("Missing value for assoc constant `{}`, which has no default", field.name)));
+            }
+        }
+    }
+    for transition in validation.attributes.transitions.iter().flatten() {
+        for variant in std::iter::once(&transition.from).chain(&transition.to) {
+            if !validation.unit_variants.iter().any(|unit_variant| unit_variant.ident == *variant) {
+                return Err(Error::new_spanned(variant, format!("`{}` is not a unit variant of {}", variant, ast.ident)));
+            }
+        }
+    }
 
-    for variant in variants {
-        let discriminant = variant.discriminant.as_ref().map(|(_, expr)| expr.clone())
-            .or_else(|| {
-                last_discriminant.clone().map(|expr| syn::parse_quote! { #expr + 1 })
-            })
-            .unwrap_or_else(|| syn::parse_quote! { 0 });
+    // Rust rejects variants sharing a discriminant, so aliases are constants holding their canonical variant
+    for (alias, target) in &validation.attributes.aliases {
+        if let Some(variant) = data_enum.variants.iter().find(|variant| variant.ident == *alias) {
+            return Err(Error::new_spanned(alias, format!("Alias `{}` has the name of a variant of {}", variant.ident, ast.ident)));
+        }
+        let index = validation.unit_variants.iter().position(|variant| variant.ident == *target)
+            .ok_or_else(|| Error::new_spanned(target, format!("`{}` is not a unit variant of {}", target, ast.ident)))?;
+        validation.unit_aliases[index].push(LitStr::new(&alias.unraw().to_string(), alias.span()));
+    }
 
-        discriminants.push(discriminant.clone());
-        last_discriminant = Some(discriminant);
+    // The "other" variant has no attribute to set values with, so it takes the defaults
+    if let (Some(field), Some(_)) = (validation.attributes.assoc.iter().find(|field| field.default.is_none()),
+                                     &validation.other_variant) {
+        return Err(Error::new_spanned(&field.name,
+                                      format!("assoc constant `{}` needs a default for the \"other\" variant", field.name)));
     }
 
-    discriminants
+    if let (Some(fallback), Some(_)) = (validation.fallback_variant, &validation.other_variant) {
+        return Err(Error::new_spanned(fallback,
+                                      "#[unit_enum(fallback)] cannot be combined with an #[unit_enum(other)] variant"));
+    }
+
+    if let (Some(maps_to), Some((other, _))) = (validation.attributes.maps_to.iter().find(|maps_to| maps_to.by_discriminant),
+                                                &validation.other_variant) {
+        return Err(Error::new_spanned(&maps_to.target,
+                                      format!("maps_to by discriminant cannot carry the \"other\" variant `{}`", other.ident)));
+    }
+
+    if let (Some(register), false) = (&validation.attributes.register, cfg!(feature = "inventory")) {
+        return Err(Error::new_spanned(register, "#[unit_enum(register)] requires the `inventory` feature of unit-enum"));
+    }
+
+    if let Some(weight) = &validation.attributes.random_weight {
+        if !cfg!(feature = "rand") {
+            return Err(Error::new_spanned(weight, "#[unit_enum(random_weight)] requires the `rand` feature of unit-enum"));
+        }
+        if !validation.attributes.assoc.iter().any(|field| field.name == *weight) {
+            return Err(Error::new_spanned(weight, format!(
+                "Unknown assoc constant `{}`. Declare it with #[unit_enum(assoc({}: Type))] on the enum", weight, weight)));
+        }
+    }
+
+    if let (SerdeMode::Ordinal, Some((other, _))) = (validation.attributes.serde_mode, &validation.other_variant) {
+        return Err(Error::new_spanned(other,
+                                      "#[unit_enum(serde = \"ordinal\")] cannot carry the payload of the \"other\" variant"));
+    }
+
+    if validation.attributes.deserialize_any && validation.attributes.serde_mode == SerdeMode::Ordinal {
+        return Err(Error::new_spanned(&ast.ident,
+                                      "#[unit_enum(deserialize_any)] cannot be combined with serde = \"ordinal\""));
+    }
+
+    if validation.attributes.known && validation.other_variant.is_none() {
+        return Err(Error::new_spanned(&ast.ident,
+                                      "#[unit_enum(known)] requires an #[unit_enum(other)] variant"));
+    }
+
+    // Every value is a discriminant of an enum with an "other" variant, so no default is safe to reserve
+    if let (Some(ExternC { sentinel: None, .. }), Some((other, _))) =
+        (&validation.attributes.extern_c, &validation.other_variant) {
+        return Err(Error::new_spanned(&other.ident,
+                                      "#[unit_enum(extern_c)] needs an explicit `sentinel` on an enum with an \"other\" variant"));
+    }
+
+    if validation.attributes.counts {
+        let other = validation.other_variant.as_ref().map(|(variant, _)| *variant);
+        for variant in validation.unit_variants.iter().copied().chain(other) {
+            count_getter(&variant.ident)?;
+        }
+    }
+
+    Ok(validation)
 }
 
-fn impl_unit_enum(
-    ast: &DeriveInput,
-    discriminant_type: &Type,
-    unit_variants: &[&Variant],
-    other_variant: Option<(&Variant, Type)>,
-) -> TokenStream {
-    let name = &ast.ident;
-    let num_variants = unit_variants.len();
-    let discriminants = compute_discriminants(unit_variants);
+/// Evaluates a discriminant made of integer and byte literals, negations and the additions of implicit discriminants.
+fn discriminant_value(expr: &Expr) -> Option<i128> {
+    match expr {
+        Expr::Lit(ExprLit { lit: Lit::Int(literal), .. }) => literal.base10_parse().ok(),
+        Expr::Lit(ExprLit { lit: Lit::Byte(literal), .. }) => Some(literal.value().into()),
+        Expr::Unary(syn::ExprUnary { op: syn::UnOp::Neg(_), expr, .. }) => discriminant_value(expr).map(|value| -value),
+        Expr::Binary(syn::ExprBinary { left, op: syn::BinOp::Add(_), right, .. }) => {
+            discriminant_value(left)?.checked_add(discriminant_value(right)?)
+        }
+        Expr::Paren(syn::ExprParen { expr, .. }) | Expr::Group(syn::ExprGroup { expr, .. }) => discriminant_value(expr),
+        _ => None,
+    }
+}
 
-    let name_impl = generate_name_impl(name, unit_variants, &other_variant);
-    let ordinal_impl = generate_ordinal_impl(name, unit_variants, &other_variant, num_variants);
-    let from_ordinal_impl = generate_from_ordinal_impl(name, unit_variants);
-    let discriminant_impl = generate_discriminant_impl(name, unit_variants, &other_variant, discriminant_type, &discriminants);
-    let from_discriminant_impl = generate_from_discriminant_impl(name, unit_variants, &other_variant, discriminant_type, &discriminants);
-    let values_impl = generate_values_impl(name, unit_variants, &discriminants, &other_variant);
+/// Whether a value or range given to `#[unit_enum(reserved = ...)]` contains `value`, if its bounds are literals.
+fn reserved_contains(reserved: &Expr, value: i128) -> Option<bool> {
+    match reserved {
+        Expr::Range(range) => {
+            let above_start = match &range.start {
+                Some(start) => value >= discriminant_value(start)?,
+                None => true,
+            };
+            let below_end = match (&range.end, range.limits) {
+                (Some(end), syn::RangeLimits::Closed(_)) => value <= discriminant_value(end)?,
+                (Some(end), syn::RangeLimits::HalfOpen(_)) => value < discriminant_value(end)?,
+                (None, _) => true,
+            };
+            Some(above_start && below_end)
+        }
+        _ => Some(discriminant_value(reserved)? == value),
+    }
+}
 
-    quote! {
-        impl #name {
-            #name_impl
+/// Rearranges the unit variants and everything collected alongside them, so that ordinal `i` is `order[i]`.
+fn reorder_unit_variants(validation: &mut ValidationResult, order: &[usize]) {
+    fn reorder<T: Clone>(items: &mut Vec<T>, order: &[usize]) {
+        *items = order.iter().map(|&index| items[index].clone()).collect();
+    }
+    reorder(&mut validation.unit_variants, order);
+    reorder(&mut validation.unit_names, order);
+    reorder(&mut validation.unit_aliases, order);
+    reorder(&mut validation.unit_codes, order);
+    reorder(&mut validation.unit_assoc, order);
+    reorder(&mut validation.unit_displays, order);
+    reorder(&mut validation.unit_discriminants, order);
+}
 
-            #ordinal_impl
+fn is_primitive_integer(ty: &Type) -> bool {
+    matches!(ty.to_token_stream().to_string().as_str(),
+             "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128" | "usize")
+}
 
-            #from_ordinal_impl
+/// The number of bits of a fixed-size primitive integer type.
+fn integer_bits(ty: &Type) -> Option<u32> {
+    match ty.to_token_stream().to_string().as_str() {
+        "i8" | "u8" => Some(8),
+        "i16" | "u16" => Some(16),
+        "i32" | "u32" => Some(32),
+        "i64" | "u64" => Some(64),
+        "i128" | "u128" => Some(128),
+        _ => None,
+    }
+}
 
-            #discriminant_impl
+/// Whether every value of `from` converts to `to` without loss, mirroring the standard `From` impls.
+fn widens_losslessly(from: &Type, to: &Type) -> bool {
+    let (from, to) = (from.to_token_stream().to_string(), to.to_token_stream().to_string());
+    from == to || matches!((from.as_str(), to.as_str()),
+        ("u8", "u16" | "u32" | "u64" | "u128" | "usize" | "i16" | "i32" | "i64" | "i128" | "isize")
+        | ("u16", "u32" | "u64" | "u128" | "usize" | "i32" | "i64" | "i128")
+        | ("u32", "u64" | "u128" | "i64" | "i128")
+        | ("u64", "u128" | "i128")
+        | ("i8", "i16" | "i32" | "i64" | "i128" | "isize")
+        | ("i16", "i32" | "i64" | "i128" | "isize")
+        | ("i32", "i64" | "i128")
+        | ("i64", "i128"))
+}
 
-            #from_discriminant_impl
+/// Whether the "other" field is declared with a type other than the repr, and so holds wider values.
+fn is_wide_other(other_type: &Type, discriminant_type: &Type) -> bool {
+    other_type.to_token_stream().to_string() != discriminant_type.to_token_stream().to_string()
+}
 
-            /// Returns the total number of unit variants in the enum (excluding the "other" variant if present).
+/// The function building the enum from a raw "other" value, which is wider than the repr when `is_wide_other`.
+fn other_value_constructor(name: &syn::Ident, other_type: &Type, discriminant_type: &Type) -> proc_macro2::TokenStream {
+    if is_wide_other(other_type, discriminant_type) {
+        quote! { #name::from_wide_discriminant }
+    } else {
+        quote! { #name::from_discriminant }
+    }
+}
+
+/// The integer type discriminants are encoded as by integrations: the "other" type when it is wider than the repr,
+/// since `discriminant()` panics on values that don't fit.
+fn encoded_discriminant_type<'a>(other_variant: &'a Option<(&Variant, Type)>, discriminant_type: &'a Type) -> &'a Type {
+    match other_variant {
+        Some((_, other_type)) if is_wide_other(other_type, discriminant_type) => other_type,
+        _ => discriminant_type,
+    }
+}
+
+/// Reads the discriminant of the `Self` or `&Self` variable `value` as `encoded_discriminant_type`.
+fn encoded_discriminant(
+    name: &syn::Ident,
+    other_variant: &Option<(&Variant, Type)>,
+    discriminant_type: &Type,
+    value: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match other_variant {
+        Some((variant, other_type)) if is_wide_other(other_type, discriminant_type) => {
+            let variant_name = &variant.ident;
+            quote! {
+                match &#value {
+                    #name::#variant_name(val) => *val,
+                    value => <#other_type as ::core::convert::From<#discriminant_type>>::from(value.discriminant()),
+                }
+            }
+        }
+        _ => quote! { #value.discriminant() },
+    }
+}
+
+/// The function building the enum back from an `encoded_discriminant`, returning `Self` when `infallible`.
+fn encoded_discriminant_constructor(
+    name: &syn::Ident,
+    other_variant: &Option<(&Variant, Type)>,
+    discriminant_type: &Type,
+) -> proc_macro2::TokenStream {
+    match other_variant {
+        Some((_, other_type)) => other_value_constructor(name, other_type, discriminant_type),
+        None => quote! { #name::from_discriminant },
+    }
+}
+
+fn get_discriminant_type(ast: &DeriveInput) -> Result<Type, Error> {
+    ast.attrs.iter()
+        .find(|attr| attr.path().is_ident("repr"))
+        .map_or(Ok(syn::parse_quote!(i32)), |attr| {
+            attr.parse_args::<Type>()
+                .map_err(|_| Error::new_spanned(attr, "Invalid repr attribute"))
+        })
+}
+
+/// The keys accepted by `#[unit_enum(...)]` on the enum, in the order `parse_container_attributes` checks them.
+const CONTAINER_KEYS: &[&str] = &[
+    "serde", "deserialize_any", "serde_rename", "strum", "arbitrary_other",
+    "sql", "ordinal_by", "napi", "extern_c",
+    "as_ref", "into_str", "from_str", "parse", "eq_str",
+    "std_derives", "display_with", "for_each_variant",
+    "generate_tests", "kani", "fuzz_helpers",
+    "is_variant", "known", "counts", "maps_to", "info", "any_trait", "assoc", "transitions",
+    "aliases", "reserved", "field", "random_weight",
+    "schema", "register", "discriminant_consts",
+];
+
+/// The integrations turned on by `#[unit_enum(key)]` on the enum, with the cargo feature of unit-enum allowing each
+/// key and whether it is enabled. Cargo unifies features across the build graph, so a feature alone adds no impls.
+const INTEGRATIONS: &[(&str, &str, bool)] = &[
+    ("arbitrary", "arbitrary", cfg!(feature = "arbitrary")),
+    ("arrow", "arrow", cfg!(feature = "arrow")),
+    ("bevy", "bevy", cfg!(feature = "bevy")),
+    ("borsh", "borsh", cfg!(feature = "borsh")),
+    ("bson", "bson", cfg!(feature = "bson")),
+    ("bytemuck", "bytemuck", cfg!(feature = "bytemuck")),
+    ("clap", "clap", cfg!(feature = "clap")),
+    ("defmt", "defmt", cfg!(feature = "defmt")),
+    ("diesel", "diesel", cfg!(feature = "diesel")),
+    ("egui", "egui", cfg!(feature = "egui")),
+    ("enum_map", "enum-map", cfg!(feature = "enum-map")),
+    ("metrics", "metrics", cfg!(feature = "metrics")),
+    ("napi", "napi", cfg!(feature = "napi")),
+    ("num_enum", "num_enum", cfg!(feature = "num_enum")),
+    ("parity_scale_codec", "parity-scale-codec", cfg!(feature = "parity-scale-codec")),
+    ("postgres_types", "postgres-types", cfg!(feature = "postgres-types")),
+    ("proptest", "proptest", cfg!(feature = "proptest")),
+    ("prost", "prost", cfg!(feature = "prost")),
+    ("quickcheck", "quickcheck", cfg!(feature = "quickcheck")),
+    ("rand", "rand", cfg!(feature = "rand")),
+    ("rayon", "rayon", cfg!(feature = "rayon")),
+    ("rkyv", "rkyv", cfg!(feature = "rkyv")),
+    ("schemars", "schemars", cfg!(feature = "schemars")),
+    ("serde", "serde", cfg!(feature = "serde")),
+    ("specta", "specta", cfg!(feature = "specta")),
+    ("sqlx", "sqlx", cfg!(feature = "sqlx")),
+    ("ts_rs", "ts-rs", cfg!(feature = "ts-rs")),
+    ("ufmt", "ufmt", cfg!(feature = "ufmt")),
+    ("uniffi", "uniffi", cfg!(feature = "uniffi")),
+    ("utoipa", "utoipa", cfg!(feature = "utoipa")),
+    ("valuable", "valuable", cfg!(feature = "valuable")),
+];
+
+/// The keys accepted by `#[unit_enum(...)]` on a unit variant.
+const UNIT_VARIANT_KEYS: &[&str] = &["fallback", "code", "assoc", "display", "ordinal"];
+
+/// The keys accepted by `#[unit_enum(...)]` on the variant with a field.
+const OTHER_VARIANT_KEYS: &[&str] = &["other"];
+
+/// Levenshtein distance between two strings, counted in chars.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let mut row: Vec<usize> = (0..=b.chars().count()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.chars().enumerate() {
+            let substitution = diagonal + usize::from(a != b);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[row.len() - 1]
+}
+
+/// Turns on the integration named by `meta`, which must be a key of `INTEGRATIONS`, if its cargo feature is enabled.
+fn enable_integration(meta: &ParseNestedMeta, integrations: &mut Vec<&'static str>) -> Result<(), Error> {
+    let &(key, feature, enabled) = INTEGRATIONS.iter()
+        .find(|(key, ..)| meta.path.is_ident(key))
+        .expect("not an integration key");
+    if !enabled {
+        return Err(meta.error(format!("#[unit_enum({})] requires the `{}` feature of unit-enum", key, feature)));
+    }
+    if !integrations.contains(&key) {
+        integrations.push(key);
+    }
+    Ok(())
+}
+
+/// Reports an unknown `#[unit_enum(...)]` key, suggesting the closest valid one for likely typos.
+fn unknown_key_error(meta: &ParseNestedMeta, position: &str, keys: &[&str]) -> Error {
+    let key = meta.path.to_token_stream().to_string().replace(' ', "");
+    // Same threshold as the generated parse errors, about one edit per three characters
+    let suggestion = keys.iter()
+        .map(|candidate| (edit_distance(&key, candidate), candidate))
+        .filter(|&(edits, candidate)| edits < candidate.len() && edits <= (candidate.len() / 3).max(1))
+        .min_by_key(|&(edits, _)| edits)
+        .map(|(_, candidate)| format!(", did you mean `{}`?", candidate))
+        .unwrap_or_else(|| ".".to_string());
+    let expected = keys.iter().map(|candidate| format!("`{}`", candidate)).collect::<Vec<_>>().join(", ");
+    let expected = if keys.len() == 1 { expected } else { format!("one of {}", expected) };
+    meta.error(format!("Unknown unit_enum attribute `{}` {}{} Expected {}", key, position, suggestion, expected))
+}
+
+fn parse_container_attributes(ast: &DeriveInput) -> Result<ContainerAttributes, Error> {
+    let mut attributes = ContainerAttributes::default();
+
+    for attr in ast.attrs.iter().filter(|attr| attr.path().is_ident("unit_enum")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("serde") {
+                enable_integration(&meta, &mut attributes.integrations)?;
+                if !meta.input.peek(Token![=]) {
+                    return Ok(());
+                }
+                let mode: LitStr = meta.value()?.parse()?;
+                attributes.serde_mode = match mode.value().as_str() {
+                    "name" => SerdeMode::Name,
+                    "discriminant" => SerdeMode::Discriminant,
+                    "auto" => SerdeMode::Auto,
+                    "ordinal" => SerdeMode::Ordinal,
+                    _ => return Err(Error::new_spanned(
+                        mode, "Invalid serde mode. Expected \"name\", \"discriminant\", \"auto\" or \"ordinal\"")),
+                };
+                Ok(())
+            } else if meta.path.is_ident("deserialize_any") {
+                attributes.deserialize_any = true;
+                Ok(())
+            } else if meta.path.is_ident("serde_rename") {
+                attributes.serde_rename = true;
+                Ok(())
+            } else if meta.path.is_ident("strum") {
+                attributes.strum = true;
+                Ok(())
+            } else if meta.path.is_ident("arbitrary_other") {
+                attributes.arbitrary_other = true;
+                Ok(())
+            } else if meta.path.is_ident("sql") {
+                let mode: LitStr = meta.value()?.parse()?;
+                attributes.sql_mode = match mode.value().as_str() {
+                    "name" => SqlMode::Name,
+                    "discriminant" => SqlMode::Discriminant,
+                    _ => return Err(Error::new_spanned(mode,
+                                                       "Invalid sql mode. Expected \"name\" or \"discriminant\"")),
+                };
+                Ok(())
+            } else if meta.path.is_ident("ordinal_by") {
+                let order: LitStr = meta.value()?.parse()?;
+                attributes.ordinal_by = match order.value().as_str() {
+                    "declaration" => OrdinalBy::Declaration,
+                    "discriminant" => OrdinalBy::Discriminant,
+                    _ => return Err(Error::new_spanned(order,
+                                                       "Invalid ordinal order. Expected \"declaration\" or \"discriminant\"")),
+                };
+                Ok(())
+            } else if meta.path.is_ident("napi") {
+                enable_integration(&meta, &mut attributes.integrations)?;
+                if !meta.input.peek(Token![=]) {
+                    return Ok(());
+                }
+                let mode: LitStr = meta.value()?.parse()?;
+                attributes.napi_mode = match mode.value().as_str() {
+                    "name" => NapiMode::Name,
+                    "discriminant" => NapiMode::Discriminant,
+                    _ => return Err(Error::new_spanned(mode,
+                                                       "Invalid napi mode. Expected \"name\" or \"discriminant\"")),
+                };
+                Ok(())
+            } else if meta.path.is_ident("extern_c") {
+                let mut extern_c = ExternC::default();
+                if meta.input.peek(syn::token::Paren) {
+                    meta.parse_nested_meta(|nested| {
+                        if nested.path.is_ident("prefix") {
+                            let prefix: LitStr = nested.value()?.parse()?;
+                            extern_c.prefix = Some(prefix.parse()
+                                .map_err(|_| Error::new_spanned(&prefix, "extern_c prefix must be a valid identifier"))?);
+                            Ok(())
+                        } else if nested.path.is_ident("sentinel") {
+                            extern_c.sentinel = Some(nested.value()?.parse()?);
+                            Ok(())
+                        } else {
+                            Err(nested.error("Invalid extern_c option. Expected `prefix` or `sentinel`"))
+                        }
+                    })?;
+                }
+                attributes.extern_c = Some(extern_c);
+                Ok(())
+            } else if meta.path.is_ident("as_ref") {
+                attributes.as_ref = true;
+                Ok(())
+            } else if meta.path.is_ident("into_str") {
+                attributes.into_str = true;
+                Ok(())
+            } else if meta.path.is_ident("from_str") {
+                attributes.from_str = true;
+                Ok(())
+            } else if meta.path.is_ident("parse") {
+                attributes.from_str = true;
+                meta.parse_nested_meta(|nested| {
+                    if nested.path.is_ident("prefix") {
+                        attributes.parse.prefix = true;
+                        Ok(())
+                    } else if nested.path.is_ident("trim") {
+                        attributes.parse.trim = true;
+                        Ok(())
+                    } else if nested.path.is_ident("separators") {
+                        attributes.parse.separators = true;
+                        Ok(())
+                    } else if nested.path.is_ident("numeric") {
+                        attributes.parse.numeric = true;
+                        Ok(())
+                    } else {
+                        Err(nested.error("Invalid parse option. Expected `prefix`, `trim`, `separators` or `numeric`"))
+                    }
+                })
+            } else if meta.path.is_ident("eq_str") {
+                attributes.eq_str = true;
+                Ok(())
+            } else if meta.path.is_ident("std_derives") {
+                attributes.std_derives = true;
+                Ok(())
+            } else if meta.path.is_ident("display_with") {
+                // Like serde's `with`, the path may be quoted
+                let value = meta.value()?;
+                attributes.display_with = Some(if value.peek(LitStr) {
+                    value.parse::<LitStr>()?.parse()?
+                } else {
+                    value.parse()?
+                });
+                Ok(())
+            } else if meta.path.is_ident("for_each_variant") {
+                attributes.for_each_variant = true;
+                Ok(())
+            } else if meta.path.is_ident("generate_tests") {
+                attributes.generate_tests = true;
+                Ok(())
+            } else if meta.path.is_ident("kani") {
+                attributes.kani = true;
+                Ok(())
+            } else if meta.path.is_ident("fuzz_helpers") {
+                attributes.fuzz_helpers = true;
+                Ok(())
+            } else if meta.path.is_ident("is_variant") {
+                attributes.is_variant = true;
+                Ok(())
+            } else if meta.path.is_ident("known") {
+                attributes.known = true;
+                Ok(())
+            } else if meta.path.is_ident("counts") {
+                attributes.counts = true;
+                Ok(())
+            } else if meta.path.is_ident("maps_to") {
+                let (target, by_discriminant) = if meta.input.peek(Token![=]) {
+                    (meta.value()?.parse()?, false)
+                } else {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    let target = content.parse()?;
+                    let mut by_discriminant = false;
+                    if content.parse::<Option<Token![,]>>()?.is_some() && !content.is_empty() {
+                        let key: syn::Ident = content.parse()?;
+                        if key != "by" {
+                            return Err(Error::new_spanned(key, "Invalid maps_to option. Expected `by`"));
+                        }
+                        content.parse::<Token![=]>()?;
+                        let mode: LitStr = content.parse()?;
+                        by_discriminant = match mode.value().as_str() {
+                            "name" => false,
+                            "discriminant" => true,
+                            _ => return Err(Error::new_spanned(mode,
+                                                               "Invalid maps_to mode. Expected \"name\" or \"discriminant\"")),
+                        };
+                    }
+                    (target, by_discriminant)
+                };
+                attributes.maps_to.push(MapsTo { target, by_discriminant });
+                Ok(())
+            } else if meta.path.is_ident("info") {
+                attributes.info = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("any_trait") {
+                attributes.any_trait = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("assoc") {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                attributes.assoc.extend(content.parse_terminated(<AssocField as syn::parse::Parse>::parse, Token![,])?);
+                Ok(())
+            } else if meta.path.is_ident("transitions") {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                attributes.transitions.get_or_insert_with(Vec::new)
+                    .extend(content.parse_terminated(<Transition as syn::parse::Parse>::parse, Token![,])?);
+                Ok(())
+            } else if meta.path.is_ident("aliases") {
+                meta.parse_nested_meta(|nested| {
+                    let alias = nested.path.require_ident()?.clone();
+                    attributes.aliases.push((alias, nested.value()?.parse()?));
+                    Ok(())
+                })
+            } else if meta.path.is_ident("reserved") {
+                attributes.reserved.push(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("field") {
+                let (mut offset, mut width, mut word) = (None, None, None);
+                meta.parse_nested_meta(|nested| {
+                    if nested.path.is_ident("offset") {
+                        offset = Some(nested.value()?.parse::<syn::LitInt>()?);
+                    } else if nested.path.is_ident("width") {
+                        width = Some(nested.value()?.parse::<syn::LitInt>()?);
+                    } else if nested.path.is_ident("word") {
+                        word = Some(nested.value()?.parse::<Type>()?);
+                    } else {
+                        return Err(nested.error("Invalid field option. Expected `offset`, `width` or `word`"));
+                    }
+                    Ok(())
+                })?;
+                let (Some(offset), Some(width)) = (offset, width) else {
+                    return Err(meta.error("#[unit_enum(field(...))] needs both `offset` and `width`"));
+                };
+                let word = word.unwrap_or_else(|| syn::parse_quote!(u32));
+                let word_bits = match word.to_token_stream().to_string().as_str() {
+                    "u8" => 8,
+                    "u16" => 16,
+                    "u32" => 32,
+                    "u64" => 64,
+                    "u128" => 128,
+                    _ => return Err(Error::new_spanned(&word, "Invalid field word. Expected u8, u16, u32, u64 or u128")),
+                };
+                let (offset_bits, width_bits) = (offset.base10_parse::<u32>()?, width.base10_parse::<u32>()?);
+                if width_bits == 0 {
+                    return Err(Error::new_spanned(&width, "The field width must be at least 1"));
+                }
+                if offset_bits.saturating_add(width_bits) > word_bits {
+                    return Err(Error::new_spanned(&offset, format!(
+                        "A {}-bit field at offset {} doesn't fit in a {}-bit word", width_bits, offset_bits, word_bits)));
+                }
+                attributes.field = Some(RegisterField {
+                    offset: offset_bits,
+                    width: width_bits,
+                    width_span: width.span(),
+                    word,
+                });
+                Ok(())
+            } else if meta.path.is_ident("random_weight") {
+                attributes.random_weight = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("schema") {
+                attributes.schema = true;
+                Ok(())
+            } else if meta.path.is_ident("register") {
+                attributes.register = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("discriminant_consts") {
+                let mut discriminant_consts = DiscriminantConsts::default();
+                if meta.input.peek(syn::token::Paren) {
+                    meta.parse_nested_meta(|nested| {
+                        if nested.path.is_ident("module") {
+                            let module: LitStr = nested.value()?.parse()?;
+                            discriminant_consts.module = Some(module.parse().map_err(|_| {
+                                Error::new_spanned(&module, "discriminant_consts module must be a valid identifier")
+                            })?);
+                            Ok(())
+                        } else {
+                            Err(nested.error("Invalid discriminant_consts option. Expected `module`"))
+                        }
+                    })?;
+                }
+                attributes.discriminant_consts = Some(discriminant_consts);
+                Ok(())
+            } else if INTEGRATIONS.iter().any(|(key, ..)| meta.path.is_ident(key)) {
+                enable_integration(&meta, &mut attributes.integrations)
+            } else {
+                let keys = CONTAINER_KEYS.iter()
+                    .chain(INTEGRATIONS.iter().map(|(key, ..)| key).filter(|key| !CONTAINER_KEYS.contains(key)))
+                    .copied()
+                    .collect::<Vec<_>>();
+                Err(unknown_key_error(&meta, "on the enum", &keys))
+            }
+        })?;
+    }
+
+    if attributes.serde_rename {
+        for attr in ast.attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename_all") {
+                    if let Some(rule) = parse_serde_spelling(&meta)? {
+                        attributes.rename_all = Some(RenameRule::from_literal(&rule)?);
+                    }
+                    Ok(())
+                } else {
+                    skip_serde_meta(&meta)
+                }
+            })?;
+        }
+    }
+
+    if attributes.strum {
+        if attributes.serde_rename {
+            return Err(Error::new_spanned(&ast.ident,
+                                          "#[unit_enum(strum)] cannot be combined with #[unit_enum(serde_rename)]"));
+        }
+        for attr in ast.attrs.iter().filter(|attr| attr.path().is_ident("strum")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("serialize_all") {
+                    attributes.strum_case = Some(StrumCase::from_literal(&meta.value()?.parse()?)?);
+                    Ok(())
+                } else if ["ascii_case_insensitive", "prefix", "suffix"].iter().any(|key| meta.path.is_ident(key)) {
+                    Err(meta.error("This strum attribute is not supported by #[unit_enum(strum)]"))
+                } else {
+                    skip_serde_meta(&meta)
+                }
+            })?;
+        }
+    }
+
+    Ok(attributes)
+}
+
+/// Reads the serialized spelling from `key = "..."` or `key(serialize = "...")` inside `#[serde]`.
+fn parse_serde_spelling(meta: &ParseNestedMeta) -> Result<Option<LitStr>, Error> {
+    if meta.input.peek(Token![=]) {
+        return Ok(Some(meta.value()?.parse()?));
+    }
+
+    let mut spelling = None;
+    meta.parse_nested_meta(|nested| {
+        let value: LitStr = nested.value()?.parse()?;
+        if nested.path.is_ident("serialize") {
+            spelling = Some(value);
+        }
+        Ok(())
+    })?;
+    Ok(spelling)
+}
+
+/// Consumes a `#[serde]` or `#[strum]` entry this derive does not care about, such as `skip` or `with = "..."`.
+fn skip_serde_meta(meta: &ParseNestedMeta) -> Result<(), Error> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.input.parse::<proc_macro2::Group>()?;
+    }
+    Ok(())
+}
+
+fn has_unit_enum_other_attr(variant: &Variant) -> Result<bool, Error> {
+    let mut other = false;
+    for attr in variant.attrs.iter().filter(|attr| attr.path().is_ident("unit_enum")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("other") {
+                if meta.input.peek(syn::token::Paren) {
+                    meta.input.parse::<proc_macro2::Group>()?;
+                }
+                other = true;
+                Ok(())
+            } else {
+                Err(unknown_key_error(&meta, "on a variant with a field", OTHER_VARIANT_KEYS))
+            }
+        })?;
+    }
+    Ok(other)
+}
+
+/// Reads the payload of `#[unit_enum(other(listed = ...))]`, with which `len()` and iteration include the "other" variant.
+fn parse_other_listed(variant: &Variant) -> Result<Option<Expr>, Error> {
+    let mut listed = None;
+    for attr in variant.attrs.iter().filter(|attr| attr.path().is_ident("unit_enum")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.input.peek(syn::token::Paren) {
+                meta.parse_nested_meta(|nested| {
+                    if nested.path.is_ident("listed") {
+                        listed = Some(nested.value()?.parse()?);
+                        Ok(())
+                    } else {
+                        Err(nested.error("Invalid unit_enum(other) option. Expected `listed = ...`"))
+                    }
+                })?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(listed)
+}
+
+/// Options set with `#[unit_enum(...)]` on a unit variant.
+#[derive(Default)]
+struct VariantAttributes {
+    fallback: bool,
+    code: Option<LitStr>,
+    assoc: Vec<(syn::Ident, Expr)>,
+    display: Option<LitStr>,
+    ordinal: Option<syn::LitInt>,
+}
+
+fn parse_variant_attributes(variant: &Variant) -> Result<VariantAttributes, Error> {
+    let mut attributes = VariantAttributes::default();
+    for attr in variant.attrs.iter().filter(|attr| attr.path().is_ident("unit_enum")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("fallback") {
+                attributes.fallback = true;
+                Ok(())
+            } else if meta.path.is_ident("code") {
+                attributes.code = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("assoc") {
+                meta.parse_nested_meta(|nested| {
+                    let name = nested.path.require_ident()?.clone();
+                    attributes.assoc.push((name, nested.value()?.parse()?));
+                    Ok(())
+                })
+            } else if meta.path.is_ident("display") {
+                attributes.display = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("ordinal") {
+                attributes.ordinal = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(unknown_key_error(&meta, "on a unit variant", UNIT_VARIANT_KEYS))
+            }
+        })?;
+    }
+    Ok(attributes)
+}
+
+fn resolve_variant_name(variant: &Variant, attributes: &ContainerAttributes) -> Result<LitStr, Error> {
+    let ident = variant.ident.unraw().to_string();
+
+    if attributes.strum {
+        // Like strum's Display, `to_string` wins over the longest `serialize`
+        let (to_string, serialize) = parse_strum_spellings(variant)?;
+        if let Some(name) = to_string.or_else(|| serialize.into_iter().max_by_key(|spelling| spelling.value().len())) {
+            return Ok(name);
+        }
+
+        if let Some(style) = attributes.strum_case {
+            return Ok(LitStr::new(&style.apply(&ident), variant.ident.span()));
+        }
+    }
+
+    if attributes.serde_rename {
+        for attr in variant.attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            let mut rename = None;
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    rename = parse_serde_spelling(&meta)?;
+                    Ok(())
+                } else {
+                    skip_serde_meta(&meta)
+                }
+            })?;
+
+            if let Some(rename) = rename {
+                return Ok(rename);
+            }
+        }
+
+        if let Some(rule) = attributes.rename_all {
+            return Ok(LitStr::new(&rule.apply(&ident), variant.ident.span()));
+        }
+    }
+
+    Ok(LitStr::new(&ident, variant.ident.span()))
+}
+
+/// Returns the spellings besides the name that `from_name()` accepts, which strum's `FromStr` parses too.
+fn resolve_variant_aliases(variant: &Variant, attributes: &ContainerAttributes, name: &LitStr) -> Result<Vec<LitStr>, Error> {
+    if !attributes.strum {
+        return Ok(Vec::new());
+    }
+
+    let (to_string, serialize) = parse_strum_spellings(variant)?;
+    Ok(serialize.into_iter().chain(to_string).filter(|spelling| spelling.value() != name.value()).collect())
+}
+
+/// Reads `#[strum(to_string = "...")]` and every `#[strum(serialize = "...")]` on a variant.
+fn parse_strum_spellings(variant: &Variant) -> Result<(Option<LitStr>, Vec<LitStr>), Error> {
+    let mut to_string = None;
+    let mut serialize = Vec::new();
+    for attr in variant.attrs.iter().filter(|attr| attr.path().is_ident("strum")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("to_string") {
+                to_string = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("serialize") {
+                serialize.push(meta.value()?.parse()?);
+                Ok(())
+            } else if ["ascii_case_insensitive", "default", "default_with", "disabled", "transparent"].iter()
+                .any(|key| meta.path.is_ident(key)) {
+                Err(meta.error("This strum attribute is not supported by #[unit_enum(strum)]"))
+            } else {
+                skip_serde_meta(&meta)
+            }
+        })?;
+    }
+    Ok((to_string, serialize))
+}
+
+/// Collects `///` doc comments into a single string, dropping the space rustdoc keeps after `///`.
+fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs.iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(meta) => match &meta.value {
+                Expr::Lit(ExprLit { lit: Lit::Str(doc), .. }) => Some(doc.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .map(|line| line.strip_prefix(' ').map(str::to_owned).unwrap_or(line))
+        .collect();
+
+    let doc = lines.join("\n").trim().to_owned();
+    (!doc.is_empty()).then_some(doc)
+}
+
+fn compute_discriminants(variants: &[&Variant]) -> Vec<Expr> {
+    let mut discriminants = Vec::with_capacity(variants.len());
+    let mut last_discriminant: Option<Expr> = None;
+
+    for variant in variants {
+        let discriminant = variant.discriminant.as_ref().map(|(_, expr)| expr.clone())
+            .or_else(|| {
+                last_discriminant.clone().map(|expr| syn::parse_quote! { #expr + 1 })
+            })
+            .unwrap_or_else(|| syn::parse_quote! { 0 });
+
+        discriminants.push(discriminant.clone());
+        last_discriminant = Some(discriminant);
+    }
+
+    discriminants
+}
+
+fn impl_unit_enum(ast: &DeriveInput, validation: ValidationResult) -> TokenStream {
+    let name = &ast.ident;
+    let discriminant_type = &validation.discriminant_type;
+    let unit_variants = &validation.unit_variants;
+    let unit_names = &validation.unit_names;
+    let other_variant = validation.other_variant;
+    let fallback_variant = validation.fallback_variant;
+    // Whether from_discriminant() maps undefined discriminants to a variant instead of failing
+    let infallible = other_variant.is_some() || fallback_variant.is_some();
+    // Whether the "other" field can hold values that don't fit in the repr
+    let wide_other = other_variant.as_ref().is_some_and(|(_, other_type)| is_wide_other(other_type, discriminant_type));
+    let encoded_type = encoded_discriminant_type(&other_variant, discriminant_type);
+    let num_variants = unit_variants.len();
+    let discriminants = validation.unit_discriminants;
+
+    let name_impl = generate_name_impl(name, unit_variants, unit_names, &other_variant, &validation.other_name);
+    let from_name_impl = generate_from_name_impl(name, unit_variants, unit_names, &validation.unit_aliases);
+    let ordinal_impl = generate_ordinal_impl(name, unit_variants, &other_variant, num_variants);
+    let discriminant_impl = generate_discriminant_impl(name, unit_variants, &other_variant, discriminant_type, &discriminants);
+    let from_discriminant_impl = generate_from_discriminant_impl(name, unit_variants, &other_variant, fallback_variant, discriminant_type, &discriminants);
+    // A listed "other" variant is yielded last, after the unit variants, so ordinals stay unchanged
+    let listed_other = other_variant.as_ref().zip(validation.other_listed.as_ref()).map(|((variant, _), payload)| {
+        let variant_name = &variant.ident;
+        quote! { #name::#variant_name(#payload) }
+    });
+    let listed_other_name = validation.other_listed.as_ref().and(validation.other_name.as_ref());
+    let len = num_variants + usize::from(listed_other.is_some());
+    let from_ordinal_impl = generate_from_ordinal_impl(name, unit_variants, listed_other.as_ref());
+    let (values_impl, iter_type) = generate_values_impl(name, &ast.vis, len);
+    let iter_names_impl = generate_iter_names_impl(unit_names, listed_other_name);
+    let values_sorted_impl = generate_values_sorted_impl(discriminant_type, &discriminants);
+    let values_with_discriminants_impl =
+        generate_values_with_discriminants_impl(name, unit_variants, discriminant_type, &discriminants);
+    let enumerate_values_impl = generate_enumerate_values_impl();
+    let steps_between_impl = generate_steps_between_impl(num_variants);
+    let ordinal_discriminant_impl = generate_ordinal_discriminant_impl(discriminant_type, &discriminants);
+    // Byte-literal discriminants (`A = b'A'`) key variants by ASCII characters, as in FIX or NMEA
+    let is_byte_literal = num_variants > 0 && discriminant_type.to_token_stream().to_string() == "u8"
+        && unit_variants.iter().all(|variant| matches!(
+            variant.discriminant,
+            Some((_, Expr::Lit(ExprLit { lit: Lit::Byte(_), .. })))
+        ));
+    let char_impl = is_byte_literal.then(|| generate_char_impl(infallible));
+    let is_variant_impl = validation.attributes.is_variant
+        .then(|| generate_is_variant_impl(name, unit_variants, &other_variant));
+    let maps_to_impls = validation.attributes.maps_to.iter().map(|maps_to| if maps_to.by_discriminant {
+        generate_maps_to_discriminant_impl(name, unit_variants, &maps_to.target, discriminant_type, &discriminants)
+    } else {
+        generate_maps_to_name_impl(name, unit_variants, &other_variant, &maps_to.target)
+    });
+    let codes: Option<Vec<LitStr>> = validation.unit_codes.iter().cloned().collect();
+    let code_impl = codes.filter(|codes| !codes.is_empty())
+        .map(|codes| generate_code_impl(name, unit_variants, &codes, &other_variant));
+    let aliases_impl = (!validation.attributes.aliases.is_empty())
+        .then(|| generate_aliases_impl(name, &validation.attributes.aliases));
+    let (register_field_impl, register_field_assertions) = validation.attributes.field.as_ref()
+        .map(|field| generate_register_field_impl(name, unit_variants, &other_variant, field, infallible, discriminant_type))
+        .unzip();
+    let reserved_impl = (!validation.attributes.reserved.is_empty())
+        .then(|| generate_reserved_impl(&validation.attributes.reserved, discriminant_type));
+    let assoc_impl = generate_assoc_impl(&validation.attributes.assoc, &validation.unit_assoc, other_variant.is_some());
+    let info_impl = validation.attributes.info.as_ref()
+        .map(|module| generate_info_impl(name, unit_variants, unit_names, module, discriminant_type));
+    let schema_impl = validation.attributes.schema.then(|| {
+        generate_schema_impl(name, unit_variants, unit_names, &validation.other_name, discriminant_type)
+    });
+    let (as_known_impl, known_enum) = other_variant.as_ref().filter(|_| validation.attributes.known)
+        .map(|(variant, _)| generate_known_impl(name, &ast.vis, unit_variants, variant, discriminant_type, &discriminants))
+        .unzip();
+    let counts_type = validation.attributes.counts
+        .then(|| generate_counts_type(name, &ast.vis, unit_variants, &other_variant));
+
+    // Byte views are only sound when the layout is pinned to a bare integer
+    let is_plain_repr = validation.has_explicit_repr && other_variant.is_none();
+    let from_ordinal_unchecked_impl = generate_from_ordinal_unchecked_impl(name, len);
+    let wide_other_impl = other_variant.as_ref().filter(|_| wide_other)
+        .map(|(variant, other_type)| generate_wide_other_impl(name, variant, other_type, discriminant_type));
+    let from_discriminant_unchecked_impl = (!infallible)
+        .then(|| generate_from_discriminant_unchecked_impl(name, is_plain_repr, discriminant_type));
+    let try_from_bytes_impl = is_plain_repr
+        .then(|| generate_try_from_bytes_impl(discriminant_type));
+    let bytemuck_impl = (validation.attributes.uses("bytemuck") && is_plain_repr)
+        .then(|| generate_bytemuck_impl(name, discriminant_type));
+    let schemars_impl = validation.attributes.uses("schemars").then(|| {
+        generate_schemars_impl(name, unit_names, &other_variant, validation.attributes.serde_mode)
+    });
+    let utoipa_impl = validation.attributes.uses("utoipa").then(|| {
+        generate_utoipa_impl(ast, unit_variants, unit_names, &other_variant, validation.attributes.serde_mode)
+    });
+    let ts_rs_impl = validation.attributes.uses("ts_rs").then(|| {
+        generate_ts_rs_impl(ast, unit_names, &other_variant, validation.attributes.serde_mode)
+    });
+    let specta_impl = validation.attributes.uses("specta").then(|| {
+        generate_specta_impl(ast, unit_variants, unit_names, &other_variant, discriminant_type, validation.attributes.serde_mode)
+    });
+    let from_discriminant_nearest_impl = (num_variants > 0)
+        .then(|| generate_from_discriminant_nearest_impl(discriminant_type, &discriminants));
+    let from_discriminant_saturating_impl = (num_variants > 0).then(|| {
+        generate_from_discriminant_saturating_impl(infallible, discriminant_type, &discriminants)
+    });
+    // An "other" variant holds any value of its field, which an EnumMap has no slot for
+    let enum_map_impl = (validation.attributes.uses("enum_map") && other_variant.is_none())
+        .then(|| generate_enum_map_impl(name, num_variants));
+    let egui_impl = validation.attributes.uses("egui")
+        .then(|| generate_egui_impl(validation.unit_displays.iter().any(Option::is_some)));
+    let par_values_impl = validation.attributes.uses("rayon").then(|| generate_par_values_impl(num_variants));
+    let arrow_impl = validation.attributes.uses("arrow").then(|| generate_arrow_impl(name, unit_names, num_variants));
+    let weight = validation.attributes.random_weight.as_ref()
+        .and_then(|weight| validation.attributes.assoc.iter().find(|field| field.name == *weight));
+    let (random_impl, rand_impl) = ((validation.attributes.uses("rand") || weight.is_some()) && num_variants > 0)
+        .then(|| generate_rand_impl(name, num_variants, weight))
+        .unzip();
+    // Test-data generators only produce "other" payloads when asked to
+    let arbitrary_other = validation.attributes.arbitrary_other && other_variant.is_some();
+    let arbitrary_impl = (validation.attributes.uses("arbitrary") && (num_variants > 0 || arbitrary_other))
+        .then(|| generate_arbitrary_impl(name, num_variants, arbitrary_other));
+    let proptest_impl = (validation.attributes.uses("proptest") && (num_variants > 0 || arbitrary_other))
+        .then(|| generate_proptest_impl(name, num_variants, arbitrary_other, discriminant_type));
+    let defmt_impl = validation.attributes.uses("defmt").then(|| {
+        generate_defmt_impl(name, unit_variants, unit_names, &other_variant, &validation.other_name, discriminant_type)
+    });
+    let ufmt_impl = validation.attributes.uses("ufmt").then(|| generate_ufmt_impl(name, &other_variant));
+    let bevy_impl = validation.attributes.uses("bevy").then(|| generate_bevy_impl(ast));
+    let valuable_impl = validation.attributes.uses("valuable").then(|| generate_valuable_impl(name, &other_variant));
+    let (metrics_label_impl, metrics_impl) = validation.attributes.uses("metrics").then(|| generate_metrics_impl(name)).unzip();
+    let borsh_impl = validation.attributes.uses("borsh")
+        .then(|| generate_borsh_impl(name, &other_variant, infallible, num_variants, discriminant_type));
+    let scale_impl = validation.attributes.uses("parity_scale_codec")
+        .then(|| generate_scale_impl(name, &other_variant, infallible, discriminant_type));
+    let rkyv_impl = validation.attributes.uses("rkyv")
+        .then(|| generate_rkyv_impl(name, &ast.vis, &other_variant, infallible, discriminant_type));
+    let bson_impl = validation.attributes.uses("bson").then(|| {
+        generate_bson_impl(name, &ast.vis, unit_names, &other_variant, infallible, wide_other, discriminant_type,
+                           validation.attributes.serde_mode)
+    });
+    let sqlx_impl = validation.attributes.uses("sqlx").then(|| match validation.attributes.sql_mode {
+        SqlMode::Name => generate_sqlx_name_impl(name, unit_variants, unit_names, &other_variant, discriminant_type),
+        SqlMode::Discriminant => {
+            generate_sqlx_discriminant_impl(name, &other_variant, infallible, num_variants, discriminant_type)
+        }
+    });
+    let diesel_impl = validation.attributes.uses("diesel").then(|| match validation.attributes.sql_mode {
+        SqlMode::Name => other_variant.is_none()
+            .then(|| generate_diesel_name_impl(name, unit_names)),
+        // ToSql borrows the encoded value, which a wide "other" field has no repr-typed copy of
+        SqlMode::Discriminant => diesel_sql_type(discriminant_type).filter(|_| !wide_other).map(|sql_type| {
+            generate_diesel_discriminant_impl(name, unit_variants, &other_variant, infallible, discriminant_type, &discriminants,
+                                              &sql_type)
+        }),
+    });
+    let postgres_impl = validation.attributes.uses("postgres_types").then(|| match validation.attributes.sql_mode {
+        SqlMode::Name => Some(generate_postgres_name_impl(name, unit_variants, unit_names, &other_variant, discriminant_type)),
+        SqlMode::Discriminant => has_postgres_type(encoded_type)
+            .then(|| generate_postgres_discriminant_impl(name, &other_variant, infallible, num_variants, discriminant_type)),
+    });
+    let (from_i32_impl, prost_impl) = (validation.attributes.uses("prost") && fits_in_i32(encoded_type))
+        .then(|| generate_prost_impl(name, &other_variant, infallible, discriminant_type))
+        .unzip();
+    // prost's i32 conversions already cover an i32 repr, which num_enum's would conflict with
+    let prost_conversions = validation.attributes.uses("prost") && discriminant_type.to_token_stream().to_string() == "i32";
+    let num_enum_impl = validation.attributes.uses("num_enum")
+        .then(|| generate_num_enum_impl(name, infallible, wide_other, prost_conversions, discriminant_type));
+    let napi_impl = validation.attributes.uses("napi").then(|| match validation.attributes.napi_mode {
+        NapiMode::Name => Some(generate_napi_name_impl(name, unit_names, &other_variant, discriminant_type)),
+        NapiMode::Discriminant => has_napi_number(encoded_type)
+            .then(|| generate_napi_discriminant_impl(name, &other_variant, infallible, num_variants, discriminant_type)),
+    });
+    let uniffi_impl = (validation.attributes.uses("uniffi") && has_uniffi_integer(encoded_type)).then(|| {
+        generate_uniffi_impl(name, &ast.vis, unit_names, &other_variant, infallible, discriminant_type)
+    });
+    let inventory_impl = validation.attributes.register.as_ref()
+        .map(|entry| generate_inventory_impl(name, unit_variants, unit_names, entry));
+    let any_trait_impl = validation.attributes.any_trait.as_ref()
+        .map(|any_trait| generate_any_trait_impl(name, unit_names, &other_variant, any_trait, discriminant_type));
+    let extern_c_impl = validation.attributes.extern_c.as_ref().map(|extern_c| {
+        generate_extern_c_impl(name, unit_variants, unit_names, &other_variant, &validation.other_name,
+                               discriminant_type, &discriminants, extern_c)
+    });
+    let as_ref_impl = validation.attributes.as_ref.then(|| quote! {
+        impl ::core::convert::AsRef<str> for #name {
+            fn as_ref(&self) -> &str {
+                self.name()
+            }
+        }
+    });
+    let into_str_impl = validation.attributes.into_str.then(|| quote! {
+        impl ::core::convert::From<#name> for &'static str {
+            fn from(value: #name) -> Self {
+                value.name()
+            }
+        }
+    });
+    let (transitions_impl, transition_error) = validation.attributes.transitions.as_ref()
+        .map(|transitions| generate_transitions_impl(name, &ast.vis, unit_variants, &other_variant, transitions))
+        .unzip();
+    let from_str_impl = validation.attributes.from_str
+        .then(|| generate_from_str_impl(name, &ast.vis, unit_names, &validation.attributes.parse, discriminant_type, infallible));
+    let (from_env_impl, env_error) = validation.attributes.from_str
+        .then(|| generate_from_env_impl(name, &ast.vis))
+        .unzip();
+    let eq_str_impl = validation.attributes.eq_str.then(|| quote! {
+        impl ::core::cmp::PartialEq<str> for #name {
+            fn eq(&self, other: &str) -> bool {
+                self.name() == other
+            }
+        }
+
+        impl ::core::cmp::PartialEq<&str> for #name {
+            fn eq(&self, other: &&str) -> bool {
+                self.name() == *other
+            }
+        }
+
+        impl ::core::cmp::PartialEq<#name> for str {
+            fn eq(&self, other: &#name) -> bool {
+                self == other.name()
+            }
+        }
+
+        impl ::core::cmp::PartialEq<#name> for &str {
+            fn eq(&self, other: &#name) -> bool {
+                *self == other.name()
+            }
+        }
+    });
+    let std_derives_impl = validation.attributes.std_derives.then(|| generate_std_derives_impl(name, &other_variant));
+    let display_str_impl = validation.unit_displays.iter().any(Option::is_some)
+        .then(|| generate_display_str_impl(name, unit_variants, unit_names, &validation.unit_displays, &other_variant));
+    // A display function wins over display texts, which it can still read with display_str()
+    let display_fmt = match (&validation.attributes.display_with, &display_str_impl) {
+        (Some(display_with), _) => Some(quote! { #display_with(self, f) }),
+        (None, Some(_)) => Some(quote! { f.pad(self.display_str()) }),
+        (None, None) => None,
+    };
+    let display_impl = display_fmt.map(|display_fmt| quote! {
+        impl ::core::fmt::Display for #name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #display_fmt
+            }
+        }
+    });
+    let for_each_variant_macro = validation.attributes.for_each_variant
+        .then(|| generate_for_each_variant_macro(name, unit_variants, unit_names, discriminant_type, &discriminants));
+    let discriminant_consts_module = validation.attributes.discriminant_consts.as_ref().map(|options| {
+        generate_discriminant_consts_module(name, &ast.vis, unit_variants, options, discriminant_type, &discriminants)
+    });
+    // Type aliases can't be compared by name, so let the compiler point at the field if the repr doesn't widen into it
+    let other_type_assertion = other_variant.as_ref().map(|(_, other_type)| {
+        quote_spanned! {other_type.span()=>
+            const _: fn(#discriminant_type) -> #other_type = <#other_type as ::core::convert::From<#discriminant_type>>::from;
+        }
+    });
+    let const_assertions = generate_const_assertions(name, unit_variants, &other_variant, infallible, discriminant_type,
+                                                     &discriminants, !validation.attributes.reserved.is_empty());
+    let tests_impl = validation.attributes.generate_tests
+        .then(|| generate_tests_impl(name, &other_variant, fallback_variant, discriminant_type, num_variants));
+    let kani_module = validation.attributes.kani.then(|| generate_kani_module(name, unit_names, &other_variant,
+                                                                              fallback_variant, discriminant_type));
+    let fuzz_impl = validation.attributes.fuzz_helpers.then(|| generate_fuzz_impl(name, &other_variant, fallback_variant,
+                                                                                  discriminant_type, num_variants));
+    let quickcheck_impl = (validation.attributes.uses("quickcheck") && (num_variants > 0 || arbitrary_other))
+        .then(|| generate_quickcheck_impl(name, num_variants, arbitrary_other, discriminant_type));
+    let clap_impl = validation.attributes.uses("clap")
+        .then(|| generate_clap_impl(name, unit_variants, unit_names, &other_variant));
+    let serde_impl = validation.attributes.uses("serde").then(|| {
+        generate_serde_impl(name, unit_names, &other_variant, infallible, num_variants, discriminant_type,
+                            validation.attributes.serde_mode, validation.attributes.deserialize_any)
+    });
+
+    quote! {
+        impl #name {
+            #name_impl
+
+            #from_name_impl
+
+            #from_env_impl
+
+            #code_impl
+
+            #display_str_impl
+
+            #assoc_impl
+
+            #aliases_impl
+            #reserved_impl
+            #register_field_impl
+
+            #transitions_impl
+
+            #ordinal_impl
+
+            #from_ordinal_impl
+
+            #from_ordinal_unchecked_impl
+
+            #discriminant_impl
+
+            #from_discriminant_impl
+
+            #wide_other_impl
+
+            #from_discriminant_unchecked_impl
+
+            #from_discriminant_nearest_impl
+
+            #from_discriminant_saturating_impl
+
+            #ordinal_discriminant_impl
+
+            #char_impl
+
+            #metrics_label_impl
+
+            #steps_between_impl
+
+            /// Returns the total number of unit variants in the enum (excluding the "other" variant if present,
+            /// unless it is marked with `#[unit_enum(other(listed = ...))]`).
             ///
             /// # Examples
             ///
@@ -211,308 +2180,5361 @@ fn impl_unit_enum(
             /// assert_eq!(Example::len(), 2);
             /// ```
             pub fn len() -> usize {
-                #num_variants
+                #len
+            }
+
+            #values_impl
+
+            #values_sorted_impl
+
+            #values_with_discriminants_impl
+
+            #enumerate_values_impl
+
+            #iter_names_impl
+
+            #try_from_bytes_impl
+
+            #random_impl
+
+            #par_values_impl
+
+            #egui_impl
+
+            #arrow_impl
+
+            #from_i32_impl
+
+            #is_variant_impl
+
+            #as_known_impl
+
+            #info_impl
+
+            #schema_impl
+
+            #fuzz_impl
+        }
+
+        #iter_type
+
+        #known_enum
+
+        #counts_type
+
+        #(#maps_to_impls)*
+
+        #other_type_assertion
+
+        #const_assertions
+        #register_field_assertions
+
+        #as_ref_impl
+
+        #into_str_impl
+
+        #from_str_impl
+
+        #env_error
+
+        #transition_error
+
+        #eq_str_impl
+
+        #std_derives_impl
+
+        #display_impl
+
+        #for_each_variant_macro
+
+        #discriminant_consts_module
+
+        #tests_impl
+
+        #kani_module
+
+        #bytemuck_impl
+
+        #enum_map_impl
+
+
+        #serde_impl
+
+        #schemars_impl
+
+        #utoipa_impl
+
+        #ts_rs_impl
+
+        #specta_impl
+
+        #clap_impl
+
+        #rand_impl
+
+        #arbitrary_impl
+
+        #proptest_impl
+
+        #quickcheck_impl
+
+        #defmt_impl
+
+        #ufmt_impl
+
+        #valuable_impl
+
+        #bevy_impl
+
+        #metrics_impl
+
+        #borsh_impl
+
+        #rkyv_impl
+
+        #scale_impl
+
+        #bson_impl
+
+        #sqlx_impl
+
+        #diesel_impl
+
+        #postgres_impl
+
+        #prost_impl
+
+        #num_enum_impl
+
+        #napi_impl
+
+        #uniffi_impl
+        #inventory_impl
+
+        #any_trait_impl
+
+        #extern_c_impl
+    }.into()
+}
+
+/// The scrutinee of a match on `&self`. Field-less enums are matched by value, which keeps the match exhaustive
+/// when the enum has no variants at all.
+fn self_scrutinee(other_variant: &Option<(&Variant, Type)>) -> proc_macro2::TokenStream {
+    match other_variant {
+        Some(_) => quote! { self },
+        None => quote! { *self },
+    }
+}
+
+fn generate_name_impl(
+    name: &syn::Ident,
+    unit_variants: &[&Variant],
+    unit_names: &[LitStr],
+    other_variant: &Option<(&Variant, Type)>,
+    other_name: &Option<LitStr>,
+) -> proc_macro2::TokenStream {
+    let scrutinee = self_scrutinee(other_variant);
+    let unit_match_arms = unit_variants.iter().zip(unit_names).map(|(variant, variant_str)| {
+        let variant_name = &variant.ident;
+        quote! { #name::#variant_name => #variant_str }
+    });
+
+    let other_arm = other_variant.as_ref().zip(other_name.as_ref()).map(|((variant, _), variant_str)| {
+        let variant_name = &variant.ident;
+        quote! { #name::#variant_name(_) => #variant_str }
+    });
+    let max_name_len = unit_names.iter().chain(other_name).map(|variant_str| variant_str.value().len()).max().unwrap_or(0);
+
+    quote! {
+        /// The length in bytes of the longest name returned by `name()`, including the "other" variant's.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum)]
+        /// enum Example {
+        ///     A,
+        ///     Bcd,
+        /// }
+        ///
+        /// assert_eq!(Example::MAX_NAME_LEN, 3);
+        /// ```
+        pub const MAX_NAME_LEN: usize = #max_name_len;
+
+        /// Returns the name of the enum variant as a string.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum)]
+        /// enum Example {
+        ///     A,
+        ///     B = 10,
+        ///     C,
+        /// }
+        ///
+        /// assert_eq!(Example::A.name(), "A");
+        /// assert_eq!(Example::B.name(), "B");
+        /// assert_eq!(Example::C.name(), "C");
+        /// ```
+        pub const fn name(&self) -> &'static str {
+            match #scrutinee {
+                #(#unit_match_arms,)*
+                #other_arm
+            }
+        }
+    }
+}
+
+fn generate_from_name_impl(
+    name: &syn::Ident,
+    unit_variants: &[&Variant],
+    unit_names: &[LitStr],
+    unit_aliases: &[Vec<LitStr>],
+) -> proc_macro2::TokenStream {
+    let match_arms = unit_variants.iter().zip(unit_names).zip(unit_aliases).map(|((variant, variant_str), aliases)| {
+        let variant_name = &variant.ident;
+        quote! { #variant_str #(| #aliases)* => Some(#name::#variant_name) }
+    });
+    let byte_match_arms = unit_variants.iter().zip(unit_names).zip(unit_aliases).map(|((variant, variant_str), aliases)| {
+        let variant_name = &variant.ident;
+        let variant_bytes = std::iter::once(variant_str).chain(aliases)
+            .map(|spelling| syn::LitByteStr::new(spelling.value().as_bytes(), spelling.span()));
+        quote! { #(#variant_bytes)|* => Some(#name::#variant_name) }
+    });
+
+    quote! {
+        /// Converts a variant name to an enum variant, if possible.
+        ///
+        /// Returns `Some(variant)` if the name matches a unit variant exactly (case-sensitive),
+        /// or `None` otherwise. The "other" variant cannot be looked up by name.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum, PartialEq, Debug)]
+        /// enum Example {
+        ///     A,
+        ///     B,
+        /// }
+        ///
+        /// assert_eq!(Example::from_name("B"), Some(Example::B));
+        /// assert_eq!(Example::from_name("b"), None);
+        /// ```
+        pub fn from_name(name: &str) -> Option<Self> {
+            match name {
+                #(#match_arms,)*
+                _ => None
+            }
+        }
+
+        /// Converts a variant name given as bytes to an enum variant, if possible.
+        ///
+        /// Like `from_name()`, but compares the bytes directly, so input such as a protocol token or a header
+        /// value doesn't have to be checked for UTF-8 first. Invalid UTF-8 never matches.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum, PartialEq, Debug)]
+        /// enum Example {
+        ///     A,
+        ///     B,
+        /// }
+        ///
+        /// assert_eq!(Example::from_name_bytes(b"B"), Some(Example::B));
+        /// assert_eq!(Example::from_name_bytes(b"\xff"), None);
+        /// ```
+        pub fn from_name_bytes(name: &[u8]) -> Option<Self> {
+            match name {
+                #(#byte_match_arms,)*
+                _ => None
+            }
+        }
+    }
+}
+
+fn generate_std_derives_impl(name: &syn::Ident, other_variant: &Option<(&Variant, Type)>) -> proc_macro2::TokenStream {
+    // Like the derives, "other" values are equal when their fields are, and never equal to a unit variant
+    let (eq, other_hash) = match other_variant {
+        Some((variant, _)) => {
+            let variant_name = &variant.ident;
+            (
+                quote! {
+                    match (self, other) {
+                        (#name::#variant_name(a), #name::#variant_name(b)) => a == b,
+                        _ => self.ordinal() == other.ordinal(),
+                    }
+                },
+                Some(quote! {
+                    if let #name::#variant_name(val) = self {
+                        ::core::hash::Hash::hash(val, state);
+                    }
+                }),
+            )
+        }
+        None => (quote! { self.ordinal() == other.ordinal() }, None),
+    };
+
+    quote! {
+        impl ::core::clone::Clone for #name {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl ::core::marker::Copy for #name {}
+
+        impl ::core::cmp::PartialEq for #name {
+            fn eq(&self, other: &Self) -> bool {
+                #eq
+            }
+        }
+
+        impl ::core::cmp::Eq for #name {}
+
+        impl ::core::hash::Hash for #name {
+            fn hash<__H: ::core::hash::Hasher>(&self, state: &mut __H) {
+                ::core::hash::Hash::hash(&::core::mem::discriminant(self), state);
+                #other_hash
+            }
+        }
+    }
+}
+
+fn generate_display_str_impl(
+    name: &syn::Ident,
+    unit_variants: &[&Variant],
+    unit_names: &[LitStr],
+    displays: &[Option<LitStr>],
+    other_variant: &Option<(&Variant, Type)>,
+) -> proc_macro2::TokenStream {
+    let scrutinee = self_scrutinee(other_variant);
+    let display_arms = unit_variants.iter().zip(unit_names).zip(displays).map(|((variant, variant_str), display)| {
+        let variant_name = &variant.ident;
+        let display = display.as_ref().unwrap_or(variant_str);
+        quote! { #name::#variant_name => #display }
+    });
+    let other_arm = other_variant.as_ref().map(|(variant, _)| {
+        let variant_name = &variant.ident;
+        quote! { #name::#variant_name(_) => self.name(), }
+    });
+
+    quote! {
+        /// Returns the text given to the variant with `#[unit_enum(display = "...")]`, which `Display` prints.
+        ///
+        /// Variants without one, including the "other" variant, return their name.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum)]
+        /// enum Example {
+        ///     #[unit_enum(display = "Not Found")]
+        ///     NotFound,
+        ///     Gone,
+        /// }
+        ///
+        /// assert_eq!(Example::NotFound.display_str(), "Not Found");
+        /// assert_eq!(Example::NotFound.name(), "NotFound");
+        /// assert_eq!(Example::Gone.display_str(), "Gone");
+        /// ```
+        pub const fn display_str(&self) -> &'static str {
+            match #scrutinee {
+                #(#display_arms,)*
+                #other_arm
+            }
+        }
+    }
+}
+
+fn generate_code_impl(
+    name: &syn::Ident,
+    unit_variants: &[&Variant],
+    codes: &[LitStr],
+    other_variant: &Option<(&Variant, Type)>,
+) -> proc_macro2::TokenStream {
+    let scrutinee = self_scrutinee(other_variant);
+    let (code_type, code_arms, other_arm) = match other_variant {
+        Some((variant, _)) => {
+            let variant_name = &variant.ident;
+            (
+                quote! { Option<&'static str> },
+                unit_variants.iter().zip(codes).map(|(variant, code)| {
+                    let variant_name = &variant.ident;
+                    quote! { #name::#variant_name => Some(#code) }
+                }).collect::<Vec<_>>(),
+                Some(quote! { #name::#variant_name(_) => None, }),
+            )
+        }
+        None => (
+            quote! { &'static str },
+            unit_variants.iter().zip(codes).map(|(variant, code)| {
+                let variant_name = &variant.ident;
+                quote! { #name::#variant_name => #code }
+            }).collect(),
+            None,
+        ),
+    };
+    let from_code_arms = unit_variants.iter().zip(codes).map(|(variant, code)| {
+        let variant_name = &variant.ident;
+        quote! { #code => Some(#name::#variant_name) }
+    });
+
+    quote! {
+        /// Returns the code given to the variant with `#[unit_enum(code = "...")]`.
+        ///
+        /// For enums with an "other" variant, returns `None` for it and `Some(code)` otherwise.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum)]
+        /// enum Example {
+        ///     #[unit_enum(code = "a")]
+        ///     A,
+        ///     #[unit_enum(code = "b")]
+        ///     B,
+        /// }
+        ///
+        /// assert_eq!(Example::B.code(), "b");
+        /// ```
+        pub const fn code(&self) -> #code_type {
+            match #scrutinee {
+                #(#code_arms,)*
+                #other_arm
+            }
+        }
+
+        /// Converts a code given with `#[unit_enum(code = "...")]` to its unit variant, if valid.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum, PartialEq, Debug)]
+        /// enum Example {
+        ///     #[unit_enum(code = "a")]
+        ///     A,
+        ///     #[unit_enum(code = "b")]
+        ///     B,
+        /// }
+        ///
+        /// assert_eq!(Example::from_code("a"), Some(Example::A));
+        /// assert_eq!(Example::from_code("A"), None);
+        /// ```
+        pub fn from_code(code: &str) -> Option<Self> {
+            match code {
+                #(#from_code_arms,)*
+                _ => None
+            }
+        }
+    }
+}
+
+fn generate_reserved_impl(reserved: &[Expr], discriminant_type: &Type) -> proc_macro2::TokenStream {
+    let checks = reserved.iter().map(|reserved| match reserved {
+        Expr::Range(range) => {
+            let above_start = range.start.as_ref().map(|start| quote! { discriminant >= (#start) });
+            let below_end = range.end.as_ref().map(|end| match range.limits {
+                syn::RangeLimits::Closed(_) => quote! { discriminant <= (#end) },
+                syn::RangeLimits::HalfOpen(_) => quote! { discriminant < (#end) },
+            });
+            match (above_start, below_end) {
+                (Some(above_start), Some(below_end)) => quote! { (#above_start && #below_end) },
+                (Some(check), None) | (None, Some(check)) => quote! { (#check) },
+                (None, None) => quote! { true },
+            }
+        }
+        _ => quote! { discriminant == (#reserved) },
+    });
+
+    quote! {
+        /// Returns whether a discriminant lies in a range reserved with `#[unit_enum(reserved = ...)]`.
+        ///
+        /// No unit variant may take a reserved discriminant, which the derive checks at compile time.
+        #[allow(unused_comparisons, clippy::absurd_extreme_comparisons, clippy::manual_range_contains)]
+        pub const fn is_reserved(discriminant: #discriminant_type) -> bool {
+            #(#checks)||*
+        }
+    }
+}
+
+fn generate_register_field_impl(
+    name: &syn::Ident,
+    unit_variants: &[&Variant],
+    other_variant: &Option<(&Variant, Type)>,
+    field: &RegisterField,
+    infallible: bool,
+    discriminant_type: &Type,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let RegisterField { offset, width, word, .. } = field;
+    let mask = proc_macro2::Literal::u128_unsuffixed(u128::MAX >> (128 - width));
+    let offset = proc_macro2::Literal::u32_unsuffixed(*offset);
+    let from_register_type = if infallible { quote! { Self } } else { quote! { Option<Self> } };
+    let from_register_doc = format!("Reads the variant from bits {} to {} of a register word, like `from_discriminant()`.",
+                                    field.offset, field.offset + width - 1);
+    let insert_doc = format!("Returns the register word with bits {} to {} replaced by the discriminant.",
+                             field.offset, field.offset + width - 1);
+    // A wide "other" field is read and written whole, as discriminant() panics on values that don't fit the repr
+    let field_type = encoded_discriminant_type(other_variant, discriminant_type);
+    let from_field = encoded_discriminant_constructor(name, other_variant, discriminant_type);
+    let discriminant = match other_variant {
+        Some((variant, other_type)) if is_wide_other(other_type, discriminant_type) => {
+            let variant_name = &variant.ident;
+            quote! {
+                match self {
+                    Self::#variant_name(val) => *val as #word,
+                    _ => self.discriminant() as #word,
+                }
+            }
+        }
+        _ => quote! { self.discriminant() as #word },
+    };
+
+    let inherent = quote! {
+        #[doc = #from_register_doc]
+        ///
+        /// The other bits of the word are ignored.
+        pub const fn from_register(register: #word) -> #from_register_type {
+            #from_field(((register >> #offset) & #mask) as #field_type)
+        }
+
+        #[doc = #insert_doc]
+        ///
+        /// The other bits of the word are kept. Only the low bits of an "other" value that fit in the field are
+        /// written.
+        pub const fn insert_into_register(&self, register: #word) -> #word {
+            (register & !(#mask << #offset)) | (((#discriminant) & #mask) << #offset)
+        }
+    };
+
+    // Discriminants that aren't literals are only known to the compiler, like the bits of usize and isize
+    let width_message = format!("The {}-bit register field is wider than `{}`", width, field_type.to_token_stream());
+    let assertions = unit_variants.iter().map(|variant| {
+        let variant_name = &variant.ident;
+        let message = format!("Discriminant of `{}` doesn't fit in the {}-bit register field", variant_name, width);
+        quote! {
+            assert!(#name::#variant_name.discriminant() as u128 & !#mask == 0, #message);
+        }
+    });
+    let assertions = quote! {
+        const _: () = {
+            assert!(#width <= <#field_type>::BITS, #width_message);
+            #(#assertions)*
+        };
+    };
+
+    (inherent, assertions)
+}
+
+fn generate_aliases_impl(name: &syn::Ident, aliases: &[(syn::Ident, syn::Ident)]) -> proc_macro2::TokenStream {
+    let consts = aliases.iter().map(|(alias, target)| {
+        let doc = format!("Alias of [`{}::{}`], which shares its discriminant.", name, target);
+        quote! {
+            #[doc = #doc]
+            #[allow(non_upper_case_globals)]
+            pub const #alias: Self = Self::#target;
+        }
+    });
+    let entries = aliases.iter().map(|(alias, target)| {
+        let alias_str = alias.unraw().to_string();
+        quote! { (#alias_str, Self::#target) }
+    });
+
+    quote! {
+        #(#consts)*
+
+        /// The names declared with `#[unit_enum(aliases(...))]`, each with the variant it stands for.
+        ///
+        /// `from_name()` accepts these names too, while `values()`, `len()` and `iter_names()` only cover the
+        /// variants themselves.
+        pub const ALIASES: &'static [(&'static str, Self)] = &[#(#entries),*];
+    }
+}
+
+fn generate_assoc_impl(
+    fields: &[AssocField],
+    unit_assoc: &[Vec<(syn::Ident, Expr)>],
+    has_other: bool,
+) -> proc_macro2::TokenStream {
+    let accessors = fields.iter().map(|field| {
+        let AssocField { name: field_name, ty, default } = field;
+        // Indexed by ordinal, so the "other" variant's default comes last
+        let values: Vec<_> = unit_assoc.iter()
+            .map(|values| values.iter().find(|(name, _)| name == field_name).map(|(_, value)| value).or(default.as_ref()))
+            .chain(has_other.then_some(default.as_ref()))
+            .collect();
+        let len = values.len();
+        let doc = format!("Returns the `{}` constant of the variant, set with `#[unit_enum(assoc({} = ...))]`.",
+                          field_name, field_name);
+        quote! {
+            #[doc = #doc]
+            pub const fn #field_name(&self) -> #ty {
+                const __VALUES: [#ty; #len] = [#(#values),*];
+                __VALUES[self.ordinal()]
+            }
+        }
+    });
+
+    quote! { #(#accessors)* }
+}
+
+/// How many valid values a parse error lists before eliding the rest.
+const MAX_EXPECTED_VALUES: usize = 10;
+
+/// Phrases the valid values for an error message, like ", expected one of {}, and 3 more", leaving a
+/// placeholder for the listed values. Empty if there are none.
+fn expected_phrase(count: usize) -> String {
+    match count {
+        0 => String::new(),
+        1 => ", expected {}".to_owned(),
+        _ if count <= MAX_EXPECTED_VALUES => ", expected one of {}".to_owned(),
+        _ => format!(", expected one of {{}}, and {} more", count - MAX_EXPECTED_VALUES),
+    }
+}
+
+/// Lists the valid names for an error message, escaped for use in a format string.
+fn expected_names(unit_names: &[LitStr]) -> String {
+    let listed = unit_names.iter().take(MAX_EXPECTED_VALUES)
+        .map(|variant_name| format!("`{}`", variant_name.value()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    expected_phrase(unit_names.len()).replacen("{}", &listed.replace('{', "{{").replace('}', "}}"), 1)
+}
+
+/// Lists the valid discriminants for an error message, as a format string suffix and the argument it expects.
+/// Discriminants can be arbitrary constant expressions, so they are formatted at run time.
+fn expected_discriminants(name: &syn::Ident, num_variants: usize) -> (String, Option<proc_macro2::TokenStream>) {
+    let listed = num_variants.min(MAX_EXPECTED_VALUES);
+    let argument = (num_variants > 0).then(|| quote! {
+        , (0..#listed).filter_map(#name::discriminant_of_ordinal)
+            .map(|discr| ::std::format!("`{}`", discr))
+            .collect::<::std::vec::Vec<_>>()
+            .join(", ")
+    });
+    (expected_phrase(num_variants), argument)
+}
+
+fn generate_from_str_impl(
+    name: &syn::Ident,
+    vis: &syn::Visibility,
+    unit_names: &[LitStr],
+    options: &ParseOptions,
+    discriminant_type: &Type,
+    infallible: bool,
+) -> proc_macro2::TokenStream {
+    let error = format_ident!("Parse{}Error", name);
+    let error_doc = format!("The error returned when parsing a [`{}`] from a string fails.", name);
+    // Only unit variants parse by name, so leave out a listed "other" variant, which from_ordinal() returns last
+    let num_variants = unit_names.len();
+    let unknown = format!("unknown {} variant `{{}}`{}", name, expected_names(unit_names));
+    let ambiguous = format!("ambiguous {} variant `{{}}`, could be {{}}", name);
+    let suggest = format!("unknown {} variant `{{}}`, did you mean `{{}}`?", name);
+
+    let input = if options.trim { quote! { s.trim() } } else { quote! { s } };
+    // `-`, `_` and spaces all compare equal, in both exact and prefix matches
+    let (separators_match, starts_with) = if options.separators {
+        (
+            Some(quote! {
+                let separator = |c: char| if matches!(c, '-' | '_' | ' ') { '_' } else { c };
+                let found = (0..#num_variants).filter_map(#name::from_ordinal)
+                    .find(|value| value.name().chars().map(separator).eq(input.chars().map(separator)));
+                if let Some(value) = found {
+                    return Ok(value);
+                }
+            }),
+            quote! {
+                |name: &str| {
+                    let mut name = name.chars().map(separator);
+                    input.chars().map(separator).all(|c| name.next() == Some(c))
+                }
+            },
+        )
+    } else {
+        (None, quote! { |name: &str| name.starts_with(input) })
+    };
+
+    // Decimal or 0x, 0o and 0b prefixed codes, resolved like from_discriminant() would
+    let numeric_match = options.numeric.then(|| {
+        let from_discriminant = if infallible {
+            quote! { return Ok(#name::from_discriminant(discr)); }
+        } else {
+            quote! {
+                if let Some(value) = #name::from_discriminant(discr) {
+                    return Ok(value);
+                }
+            }
+        };
+        quote! {
+            let discr = match input.get(..2) {
+                Some("0x" | "0X") => <#discriminant_type>::from_str_radix(&input[2..], 16).ok(),
+                Some("0o" | "0O") => <#discriminant_type>::from_str_radix(&input[2..], 8).ok(),
+                Some("0b" | "0B") => <#discriminant_type>::from_str_radix(&input[2..], 2).ok(),
+                _ => input.parse::<#discriminant_type>().ok(),
+            };
+            if let Some(discr) = discr {
+                #from_discriminant
+            }
+        }
+    });
+
+    // Like git abbreviations, a prefix only resolves when exactly one name starts with it
+    let prefix_match = options.prefix.then(|| quote! {
+        if !input.is_empty() {
+            let starts_with = #starts_with;
+            let mut matches = (0..#num_variants).filter_map(#name::from_ordinal).filter(|value| starts_with(value.name()));
+            match (matches.next(), matches.next()) {
+                (Some(value), None) => return Ok(value),
+                (Some(first), Some(second)) => {
+                    let candidates = [first, second].into_iter().chain(matches).map(|value| value.name()).collect();
+                    return Err(#error { input: s.to_owned(), candidates, suggestion: None });
+                }
+                (None, _) => {}
+            }
+        }
+    });
+
+    quote! {
+        #[doc = #error_doc]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #vis struct #error {
+            input: ::std::string::String,
+            candidates: ::std::vec::Vec<&'static str>,
+            suggestion: ::core::option::Option<&'static str>,
+        }
+
+        impl #error {
+            /// Returns the string that failed to parse.
+            pub fn input(&self) -> &str {
+                &self.input
+            }
+
+            /// Returns the names the input is an ambiguous prefix of, or an empty slice if nothing matched.
+            pub fn candidates(&self) -> &[&'static str] {
+                &self.candidates
+            }
+
+            /// Returns the variant name closest to the input, if one is close enough to be a likely typo.
+            pub fn suggestion(&self) -> ::core::option::Option<&'static str> {
+                self.suggestion
+            }
+
+            fn unknown(input: &str) -> Self {
+                // Levenshtein distance, allowing about one edit per three characters of the name
+                let distance = |candidate: &str| {
+                    let mut row: ::std::vec::Vec<usize> = (0..=candidate.chars().count()).collect();
+                    for (i, a) in input.chars().enumerate() {
+                        let mut diagonal = row[0];
+                        row[0] = i + 1;
+                        for (j, b) in candidate.chars().enumerate() {
+                            let substitution = diagonal + usize::from(a != b);
+                            diagonal = row[j + 1];
+                            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+                        }
+                    }
+                    row[row.len() - 1]
+                };
+                let suggestion = (0..#num_variants).filter_map(#name::from_ordinal)
+                    .map(|value| value.name())
+                    .map(|candidate| (distance(candidate), candidate))
+                    .filter(|&(edits, candidate)| {
+                        let len = candidate.chars().count();
+                        edits < len && edits <= (len / 3).max(1)
+                    })
+                    .min_by_key(|&(edits, _)| edits)
+                    .map(|(_, candidate)| candidate);
+
+                #error { input: input.to_owned(), candidates: ::std::vec::Vec::new(), suggestion }
+            }
+        }
+
+        impl ::core::fmt::Display for #error {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                if !self.candidates.is_empty() {
+                    ::core::write!(f, #ambiguous, self.input, self.candidates.join(" or "))
+                } else if let Some(suggestion) = self.suggestion {
+                    ::core::write!(f, #suggest, self.input, suggestion)
+                } else {
+                    ::core::write!(f, #unknown, self.input)
+                }
+            }
+        }
+
+        impl ::std::error::Error for #error {}
+
+        impl ::core::str::FromStr for #name {
+            type Err = #error;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                let input = #input;
+                if let Some(value) = #name::from_name(input) {
+                    return Ok(value);
+                }
+                #separators_match
+                #numeric_match
+                #prefix_match
+                Err(#error::unknown(s))
+            }
+        }
+
+        impl ::core::convert::TryFrom<&str> for #name {
+            type Error = #error;
+
+            fn try_from(value: &str) -> ::core::result::Result<Self, Self::Error> {
+                value.parse()
+            }
+        }
+    }
+}
+
+fn generate_from_env_impl(name: &syn::Ident, vis: &syn::Visibility) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let error = format_ident!("{}EnvError", name);
+    let parse_error = format_ident!("Parse{}Error", name);
+    let error_doc = format!("The error returned when reading a [`{}`] from an environment variable fails.", name);
+
+    let from_env_impl = quote! {
+        /// Reads the environment variable `var` and parses its value like `FromStr`.
+        ///
+        /// Fails if the variable is not set, is not valid unicode, or doesn't hold a valid variant name, in which
+        /// case the error lists the valid names like the parse error does.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum)]
+        /// #[unit_enum(from_str)]
+        /// enum Mode {
+        ///     Fast,
+        ///     Safe,
+        /// }
+        ///
+        /// let mode = Mode::from_env("APP_MODE").unwrap_or(Mode::Safe);
+        /// ```
+        pub fn from_env(var: &str) -> ::core::result::Result<Self, #error> {
+            match ::std::env::var(var) {
+                Ok(value) => value.parse().map_err(|error| #error::Invalid(var.to_owned(), error)),
+                Err(::std::env::VarError::NotPresent) => Err(#error::NotPresent(var.to_owned())),
+                Err(::std::env::VarError::NotUnicode(_)) => Err(#error::NotUnicode(var.to_owned())),
+            }
+        }
+    };
+
+    let env_error = quote! {
+        #[doc = #error_doc]
+        ///
+        /// Each variant holds the name of the environment variable.
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #vis enum #error {
+            /// The variable is not set.
+            NotPresent(::std::string::String),
+            /// The value of the variable is not valid unicode.
+            NotUnicode(::std::string::String),
+            /// The value of the variable is not a valid variant name.
+            Invalid(::std::string::String, #parse_error),
+        }
+
+        impl ::core::fmt::Display for #error {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match self {
+                    #error::NotPresent(var) => ::core::write!(f, "environment variable `{}` is not set", var),
+                    #error::NotUnicode(var) => ::core::write!(f, "environment variable `{}` is not valid unicode", var),
+                    #error::Invalid(var, error) => ::core::write!(f, "environment variable `{}`: {}", var, error),
+                }
+            }
+        }
+
+        impl ::std::error::Error for #error {
+            fn source(&self) -> ::core::option::Option<&(dyn ::std::error::Error + 'static)> {
+                match self {
+                    #error::Invalid(_, error) => Some(error),
+                    _ => None,
+                }
+            }
+        }
+    };
+
+    (from_env_impl, env_error)
+}
+
+fn generate_transitions_impl(
+    name: &syn::Ident,
+    vis: &syn::Visibility,
+    unit_variants: &[&Variant],
+    other_variant: &Option<(&Variant, Type)>,
+    transitions: &[Transition],
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let scrutinee = self_scrutinee(other_variant);
+    let other_arm = other_variant.as_ref().map(|(variant, _)| {
+        let variant_name = &variant.ident;
+        quote! { #name::#variant_name(_) => &[], }
+    });
+    let error = format_ident!("{}TransitionError", name);
+    let error_doc = format!("The error returned when a [`{}`] transition is not allowed.", name);
+    let invalid = format!("invalid {} transition from `{{}}` to `{{}}`", name);
+
+    // Successors are kept in declaration order, without duplicates, whatever order the edges are listed in
+    let successors: Vec<Vec<usize>> = unit_variants.iter().map(|variant| {
+        let targets: Vec<_> = transitions.iter()
+            .filter(|transition| transition.from == variant.ident)
+            .flat_map(|transition| &transition.to)
+            .collect();
+        (0..unit_variants.len()).filter(|&ordinal| targets.contains(&&unit_variants[ordinal].ident)).collect()
+    }).collect();
+    let edges = unit_variants.iter().zip(&successors).flat_map(|(variant, targets)| {
+        let from = &variant.ident;
+        targets.iter().map(move |&ordinal| {
+            let to = &unit_variants[ordinal].ident;
+            quote! { (#name::#from, #name::#to) }
+        })
+    }).collect::<Vec<_>>();
+    let can_transition_body = if edges.is_empty() {
+        quote! { false }
+    } else {
+        quote! { matches!((self, to), #(#edges)|*) }
+    };
+    let successor_arms = unit_variants.iter().zip(&successors).map(|(variant, targets)| {
+        let variant_name = &variant.ident;
+        quote! { #name::#variant_name => &[#(#targets),*] }
+    });
+
+    let transitions_impl = quote! {
+        /// Returns `true` if `#[unit_enum(transitions(...))]` allows going from this variant to `to`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum)]
+        /// #[unit_enum(transitions(A -> B))]
+        /// enum Example {
+        ///     A,
+        ///     B,
+        /// }
+        ///
+        /// assert!(Example::A.can_transition_to(&Example::B));
+        /// assert!(!Example::B.can_transition_to(&Example::A));
+        /// ```
+        pub const fn can_transition_to(&self, to: &Self) -> bool {
+            #can_transition_body
+        }
+
+        /// Returns an iterator over the variants this variant can transition to, in declaration order.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum, PartialEq, Debug)]
+        /// #[unit_enum(transitions(A -> [B, C]))]
+        /// enum Example {
+        ///     A,
+        ///     B,
+        ///     C,
+        /// }
+        ///
+        /// assert_eq!(Example::A.successors().collect::<Vec<_>>(), vec![Example::B, Example::C]);
+        /// ```
+        pub fn successors(&self) -> impl Iterator<Item = Self> {
+            let ordinals: &'static [usize] = match #scrutinee {
+                #(#successor_arms,)*
+                #other_arm
+            };
+            ordinals.iter().filter_map(|&ordinal| #name::from_ordinal(ordinal))
+        }
+
+        /// Returns `to` if this variant can transition to it, or an error holding both variants otherwise.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum, PartialEq, Debug)]
+        /// #[unit_enum(transitions(A -> B))]
+        /// enum Example {
+        ///     A,
+        ///     B,
+        /// }
+        ///
+        /// assert_eq!(Example::A.transition_to(Example::B).ok(), Some(Example::B));
+        /// assert!(Example::B.transition_to(Example::A).is_err());
+        /// ```
+        pub fn transition_to(self, to: Self) -> ::core::result::Result<Self, #error> {
+            if self.can_transition_to(&to) {
+                Ok(to)
+            } else {
+                Err(#error { from: self, to })
+            }
+        }
+    };
+
+    let transition_error = quote! {
+        #[doc = #error_doc]
+        #vis struct #error {
+            from: #name,
+            to: #name,
+        }
+
+        impl #error {
+            /// Returns the variant the transition started from.
+            pub fn from(&self) -> &#name {
+                &self.from
+            }
+
+            /// Returns the variant the transition was not allowed to go to.
+            pub fn to(&self) -> &#name {
+                &self.to
+            }
+        }
+
+        impl ::core::fmt::Debug for #error {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_struct(::core::stringify!(#error))
+                    .field("from", &self.from.name())
+                    .field("to", &self.to.name())
+                    .finish()
+            }
+        }
+
+        impl ::core::fmt::Display for #error {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::write!(f, #invalid, self.from.name(), self.to.name())
+            }
+        }
+
+        impl ::std::error::Error for #error {}
+    };
+
+    (transitions_impl, transition_error)
+}
+
+fn generate_for_each_variant_macro(
+    name: &syn::Ident,
+    unit_variants: &[&Variant],
+    unit_names: &[LitStr],
+    discriminant_type: &Type,
+    discriminants: &[Expr],
+) -> proc_macro2::TokenStream {
+    let macro_name = format_ident!("for_each_{}_variant", StrumCase::Snake.apply(&name.unraw().to_string()));
+    let invocations = unit_variants.iter().zip(unit_names).zip(discriminants).enumerate()
+        .map(|(index, ((variant, variant_name), discriminant))| {
+            let ident = &variant.ident;
+            let ordinal = proc_macro2::Literal::usize_unsuffixed(index);
+            quote! { $callback!(#ident, #variant_name, #ordinal, (#discriminant) as #discriminant_type); }
+        });
+
+    quote! {
+        #[allow(unused_macros)]
+        macro_rules! #macro_name {
+            ($callback:ident) => {
+                #(#invocations)*
+            };
+        }
+    }
+}
+
+fn generate_discriminant_consts_module(
+    name: &syn::Ident,
+    vis: &syn::Visibility,
+    unit_variants: &[&Variant],
+    options: &DiscriminantConsts,
+    discriminant_type: &Type,
+    discriminants: &[Expr],
+) -> proc_macro2::TokenStream {
+    let snake_name = StrumCase::Snake.apply(&name.unraw().to_string());
+    let module = options.module.clone().unwrap_or_else(|| format_ident!("{}_discriminants", snake_name));
+    let module_doc = format!("The discriminants of the [`{}`] variants, as constants.", name);
+    // Literal values rather than paths through `super`, which can't name an enum declared inside a function
+    let consts = unit_variants.iter().zip(discriminants).map(|(variant, discriminant)| {
+        let variant_name = &variant.ident;
+        let const_name = format_ident!("{}", StrumCase::ScreamingSnake.apply(&variant_name.unraw().to_string()));
+        let doc = format!("The discriminant of [`{}::{}`](super::{}::{}).", name, variant_name, name, variant_name);
+        let value = discriminant_value(discriminant).expect("literal discriminant");
+        let magnitude = proc_macro2::Literal::u128_unsuffixed(value.unsigned_abs());
+        let value = if value < 0 { quote! { -#magnitude } } else { quote! { #magnitude } };
+        quote! {
+            #[doc = #doc]
+            pub const #const_name: #discriminant_type = #value;
+        }
+    });
+
+    quote! {
+        #[doc = #module_doc]
+        #[allow(dead_code)]
+        #vis mod #module {
+            #(#consts)*
+        }
+    }
+}
+
+/// Checks at compile time that the generated methods agree with each other and with the compiler's discriminants.
+fn generate_const_assertions(
+    name: &syn::Ident,
+    unit_variants: &[&Variant],
+    other_variant: &Option<(&Variant, Type)>,
+    infallible: bool,
+    discriminant_type: &Type,
+    discriminants: &[Expr],
+    reserved: bool,
+) -> proc_macro2::TokenStream {
+    let assertions = unit_variants.iter().zip(discriminants).enumerate().map(|(index, (variant, discriminant))| {
+        let variant_name = &variant.ident;
+        let reserved_assertion = reserved.then(|| {
+            let message = format!("Discriminant of `{}` is reserved by #[unit_enum(reserved = ...)]", variant_name);
+            quote! {
+                assert!(!#name::is_reserved(#name::#variant_name.discriminant()), #message);
+            }
+        });
+        // Only field-less enums can be cast to read the discriminant the compiler assigned
+        let compiler_discriminant = other_variant.is_none().then(|| quote! {
+            assert!(#name::#variant_name as #discriminant_type == (#discriminant) as #discriminant_type);
+        });
+        let found = if infallible {
+            quote! { #name::#variant_name }
+        } else {
+            quote! { Some(#name::#variant_name) }
+        };
+        quote! {
+            #compiler_discriminant
+            #reserved_assertion
+            assert!(#name::#variant_name.ordinal() == #index);
+            assert!(matches!(#name::from_ordinal(#index), Some(#name::#variant_name)));
+            assert!(matches!(#name::from_discriminant(#name::#variant_name.discriminant()), #found));
+            assert!(matches!(#name::discriminant_of_ordinal(#index), Some(d) if d == #name::#variant_name.discriminant()));
+            assert!(matches!(#name::ordinal_of_discriminant(#name::#variant_name.discriminant()), Some(#index)));
+        }
+    });
+
+    quote! {
+        const _: () = {
+            #(#assertions)*
+        };
+    }
+}
+
+fn generate_tests_impl(
+    name: &syn::Ident,
+    other_variant: &Option<(&Variant, Type)>,
+    fallback_variant: Option<&Variant>,
+    discriminant_type: &Type,
+    num_variants: usize,
+) -> proc_macro2::TokenStream {
+    // Free functions next to the enum rather than a module, whose `super` can't name an enum declared in a function
+    let snake_name = StrumCase::Snake.apply(&name.unraw().to_string());
+    let test = |check: &str| format_ident!("{}_unit_enum_{}", snake_name, check);
+    // The unit variants, leaving out a listed "other" variant
+    let unit_values = quote! { #name::values().take(#num_variants) };
+    let from_discriminant_ordinal = if other_variant.is_some() || fallback_variant.is_some() {
+        quote! { Some(#name::from_discriminant(value.discriminant()).ordinal()) }
+    } else {
+        quote! { #name::from_discriminant(value.discriminant()).map(|found| found.ordinal()) }
+    };
+    let other_test = other_variant.as_ref().map(|_| {
+        let test_name = test("undefined_discriminants_are_other");
+        quote! {
+            #[cfg(test)]
+            #[test]
+            fn #test_name() {
+                let defined: ::std::vec::Vec<#discriminant_type> = #unit_values.map(|value| value.discriminant()).collect();
+                let undefined = (0..=#num_variants).map(|index| index as #discriminant_type)
+                    .find(|discriminant| !defined.contains(discriminant));
+                if let Some(discriminant) = undefined {
+                    let other = #name::from_discriminant(discriminant);
+                    assert_eq!(other.discriminant(), discriminant);
+                    assert_eq!(other.ordinal(), #num_variants);
+                    assert_eq!(#name::from_ordinal(other.ordinal()).is_some(), #name::len() > #num_variants);
+                }
+            }
+        }
+    });
+
+    let fallback_test = fallback_variant.map(|variant| {
+        let variant_name = &variant.ident;
+        let test_name = test("undefined_discriminants_are_fallback");
+        quote! {
+            #[cfg(test)]
+            #[test]
+            fn #test_name() {
+                let defined: ::std::vec::Vec<#discriminant_type> = #unit_values.map(|value| value.discriminant()).collect();
+                let undefined = (0..=#num_variants).map(|index| index as #discriminant_type)
+                    .find(|discriminant| !defined.contains(discriminant));
+                if let Some(discriminant) = undefined {
+                    assert_eq!(#name::from_discriminant(discriminant).ordinal(), #name::#variant_name.ordinal());
+                }
+            }
+        }
+    });
+
+    let ordinals_round_trip = test("ordinals_round_trip");
+    let discriminants_round_trip = test("discriminants_round_trip");
+    let names_round_trip = test("names_round_trip");
+    let names_and_discriminants_are_unique = test("names_and_discriminants_are_unique");
+    quote! {
+        #[cfg(test)]
+        #[test]
+        fn #ordinals_round_trip() {
+            assert_eq!(#name::values().count(), #name::len());
+            for (index, value) in #unit_values.enumerate() {
+                assert_eq!(value.ordinal(), index, "ordinal of {}", value.name());
+                assert_eq!(#name::from_ordinal(index).map(|found| found.name()), Some(value.name()));
+            }
+            assert!(#name::from_ordinal(#name::len()).is_none());
+        }
+
+        #[cfg(test)]
+        #[test]
+        fn #discriminants_round_trip() {
+            for value in #unit_values {
+                assert_eq!(#from_discriminant_ordinal, Some(value.ordinal()), "discriminant of {}", value.name());
+            }
+        }
+
+        #[cfg(test)]
+        #[test]
+        fn #names_round_trip() {
+            for value in #unit_values {
+                assert_eq!(#name::from_name(value.name()).map(|found| found.ordinal()), Some(value.ordinal()));
+            }
+        }
+
+        #[cfg(test)]
+        #[test]
+        fn #names_and_discriminants_are_unique() {
+            for (index, value) in #unit_values.enumerate() {
+                for other in #unit_values.skip(index + 1) {
+                    assert_ne!(value.name(), other.name());
+                    assert_ne!(value.discriminant(), other.discriminant(), "{} and {}", value.name(), other.name());
+                }
+            }
+        }
+
+        #other_test
+
+        #fallback_test
+    }
+}
+
+fn generate_kani_module(
+    name: &syn::Ident,
+    unit_names: &[LitStr],
+    other_variant: &Option<(&Variant, Type)>,
+    fallback_variant: Option<&Variant>,
+    discriminant_type: &Type,
+) -> proc_macro2::TokenStream {
+    let num_variants = unit_names.len();
+    let module = format_ident!("{}_unit_enum_proofs", StrumCase::Snake.apply(&name.unraw().to_string()));
+    let undefined_check = match (other_variant, fallback_variant) {
+        (Some(_), _) => quote! {
+            let other = #name::from_discriminant(discr);
+            assert_eq!(other.discriminant(), discr);
+            assert_eq!(other.ordinal(), #num_variants);
+        },
+        (None, Some(variant)) => {
+            let variant_name = &variant.ident;
+            quote! { assert_eq!(#name::from_discriminant(discr).ordinal(), #name::#variant_name.ordinal()); }
+        }
+        (None, None) => quote! { assert!(#name::from_discriminant(discr).is_none()); },
+    };
+    let defined_check = if other_variant.is_some() || fallback_variant.is_some() {
+        quote! { assert_eq!(#name::from_discriminant(discr).ordinal(), ord); }
+    } else {
+        quote! {
+            assert_eq!(#name::from_discriminant(discr).map(|found| found.ordinal()), Some(ord));
+            // SAFETY: `discr` is the discriminant of the variant at `ord`
+            assert_eq!(unsafe { #name::from_discriminant_unchecked(discr) }.ordinal(), ord);
+        }
+    };
+    // Comparing a variant name loops at most once per byte of it, so bound the unwinding by the longest one.
+    let unwind = unit_names.iter().map(|name| name.value().len()).max().unwrap_or(0) + 2;
+    let name_proof = (num_variants > 0).then(|| quote! {
+        #[cfg(kani)]
+        #[kani::proof]
+        #[kani::unwind(#unwind)]
+        fn names_round_trip() {
+            let ord: usize = kani::any();
+            kani::assume(ord < #num_variants);
+            let value = #name::from_ordinal(ord).unwrap();
+            assert_eq!(#name::from_name(value.name()).map(|found| found.ordinal()), Some(ord));
+        }
+    });
+
+    quote! {
+        // `kani` is only set by `cargo kani`, so the `cfg` is unknown to rustc otherwise.
+        #[allow(unexpected_cfgs)]
+        mod #module {
+            #[cfg(kani)]
+            use super::#name;
+
+            #[cfg(kani)]
+            #[kani::proof]
+            fn ordinals_round_trip() {
+                let ord: usize = kani::any();
+                match #name::from_ordinal(ord) {
+                    Some(value) => assert_eq!(value.ordinal(), ord),
+                    None => assert!(ord >= #num_variants),
+                }
+            }
+
+            #[cfg(kani)]
+            #[kani::proof]
+            fn discriminants_round_trip() {
+                let discr: #discriminant_type = kani::any();
+                match #name::ordinal_of_discriminant(discr) {
+                    Some(ord) => {
+                        assert_eq!(#name::discriminant_of_ordinal(ord), Some(discr));
+                        assert_eq!(#name::from_ordinal(ord).map(|found| found.discriminant()), Some(discr));
+                        #defined_check
+                    }
+                    None => {
+                        #undefined_check
+                    }
+                }
+            }
+
+            #name_proof
+        }
+    }
+}
+
+fn generate_fuzz_impl(
+    name: &syn::Ident,
+    other_variant: &Option<(&Variant, Type)>,
+    fallback_variant: Option<&Variant>,
+    discriminant_type: &Type,
+    num_variants: usize,
+) -> proc_macro2::TokenStream {
+    let found = if other_variant.is_some() || fallback_variant.is_some() {
+        quote! { #name::from_discriminant(discriminant) }
+    } else {
+        quote! { #name::from_discriminant(discriminant).expect("defined discriminant") }
+    };
+    let undefined_check = match (other_variant, fallback_variant) {
+        (Some(_), _) => quote! {
+            let other = #name::from_discriminant(discriminant);
+            assert_eq!(other.discriminant(), discriminant);
+            assert_eq!(other.ordinal(), #num_variants);
+        },
+        (None, Some(variant)) => {
+            let variant_name = &variant.ident;
+            quote! { assert_eq!(#name::from_discriminant(discriminant).ordinal(), #name::#variant_name.ordinal()); }
+        }
+        (None, None) => quote! { assert!(#name::from_discriminant(discriminant).is_none()); },
+    };
+
+    quote! {
+        /// Fuzz target helper that reads a discriminant from the leading bytes of `data` in little-endian order,
+        /// padding short input with zeros, and checks it against `from_discriminant()`, `ordinal_of_discriminant()`
+        /// and `discriminant_of_ordinal()`.
+        ///
+        /// Panics if a check fails.
+        pub fn fuzz_from_discriminant_round_trip(data: &[u8]) {
+            let mut bytes = [0u8; ::core::mem::size_of::<#discriminant_type>()];
+            let len = data.len().min(bytes.len());
+            bytes[..len].copy_from_slice(&data[..len]);
+            let discriminant = <#discriminant_type>::from_le_bytes(bytes);
+            match #name::ordinal_of_discriminant(discriminant) {
+                Some(ordinal) => {
+                    let value = #found;
+                    assert_eq!(value.ordinal(), ordinal);
+                    assert_eq!(value.discriminant(), discriminant);
+                    assert_eq!(#name::discriminant_of_ordinal(ordinal), Some(discriminant));
+                }
+                None => {
+                    #undefined_check
+                }
+            }
+        }
+
+        /// Fuzz target helper that checks that `from_name_bytes()` agrees with `from_name()` on `data`, and that the
+        /// name of a variant found either way leads back to it.
+        ///
+        /// Panics if a check fails.
+        pub fn fuzz_from_name_round_trip(data: &[u8]) {
+            let found = #name::from_name_bytes(data);
+            let from_str = ::core::str::from_utf8(data).ok().and_then(#name::from_name);
+            assert_eq!(found.as_ref().map(#name::ordinal), from_str.as_ref().map(#name::ordinal));
+            if let Some(value) = found {
+                assert_eq!(#name::from_name(value.name()).map(|found| found.ordinal()), Some(value.ordinal()));
+            }
+        }
+
+        /// Fuzz target helper running every check on `data`, for a single fuzz target covering the enum.
+        ///
+        /// Panics if a check fails.
+        pub fn fuzz_round_trip(data: &[u8]) {
+            #name::fuzz_from_discriminant_round_trip(data);
+            #name::fuzz_from_name_round_trip(data);
+        }
+    }
+}
+
+fn generate_ordinal_impl(
+    name: &syn::Ident,
+    unit_variants: &[&Variant],
+    other_variant: &Option<(&Variant, Type)>,
+    num_variants: usize,
+) -> proc_macro2::TokenStream {
+    let scrutinee = self_scrutinee(other_variant);
+    let unit_match_arms = unit_variants.iter().enumerate().map(|(index, variant)| {
+        let variant_name = &variant.ident;
+        quote! { #name::#variant_name => #index }
+    });
+
+    let other_arm = other_variant.as_ref().map(|(variant, _)| {
+        let variant_name = &variant.ident;
+        quote! { #name::#variant_name(_) => #num_variants }
+    });
+
+    quote! {
+        /// Returns the zero-based ordinal of the enum variant.
+        ///
+        /// For enums with an "other" variant, it returns the position after all unit variants.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum)]
+        /// enum Example {
+        ///     A,      // ordinal: 0
+        ///     B = 10, // ordinal: 1
+        ///     C,      // ordinal: 2
+        /// }
+        ///
+        /// assert_eq!(Example::A.ordinal(), 0);
+        /// assert_eq!(Example::B.ordinal(), 1);
+        /// assert_eq!(Example::C.ordinal(), 2);
+        /// ```
+        pub const fn ordinal(&self) -> usize {
+            match #scrutinee {
+                #(#unit_match_arms,)*
+                #other_arm
+            }
+        }
+    }
+}
+fn generate_from_ordinal_impl(
+    name: &syn::Ident,
+    unit_variants: &[&Variant],
+    listed_other: Option<&proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let match_arms = unit_variants.iter().enumerate().map(|(index, variant)| {
+        let variant_name = &variant.ident;
+        quote! { #index => Some(#name::#variant_name) }
+    });
+    // A listed "other" variant takes the ordinal after the unit variants, which ordinal() returns for it
+    let num_variants = unit_variants.len();
+    let listed_other_arm = listed_other.map(|listed_other| quote! { #num_variants => Some(#listed_other), });
+
+    quote! {
+        /// Converts a zero-based ordinal to an enum variant, if possible.
+        ///
+        /// Returns `Some(variant)` if the ordinal corresponds to a unit variant, or to an "other" variant marked with
+        /// `#[unit_enum(other(listed = ...))]`, which is returned with the listed payload. Returns `None` if the
+        /// ordinal is out of range or would correspond to an unlisted "other" variant.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// # #[derive(Debug, PartialEq)]
+        /// #[derive(UnitEnum)]
+        /// enum Example {
+        ///     A,
+        ///     B,
+        ///     #[unit_enum(other)]
+        ///     Other(i32),
+        /// }
+        ///
+        /// assert_eq!(Example::from_ordinal(0), Some(Example::A));
+        /// assert_eq!(Example::from_ordinal(2), None); // Other variant
+        /// assert_eq!(Example::from_ordinal(99), None); // Out of range
+        /// ```
+        pub const fn from_ordinal(ord: usize) -> Option<Self> {
+            match ord {
+                #(#match_arms,)*
+                #listed_other_arm
+                _ => None
+            }
+        }
+    }
+}
+
+fn generate_from_ordinal_unchecked_impl(name: &syn::Ident, len: usize) -> proc_macro2::TokenStream {
+    let invalid = format!("ordinal out of range for {}", name);
+
+    quote! {
+        /// Converts a zero-based ordinal to a variant like `from_ordinal()`, without checking that it is in range.
+        ///
+        /// Debug builds still check the ordinal and panic if it is out of range.
+        ///
+        /// # Safety
+        ///
+        /// `ord` must be less than `len()`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum, PartialEq, Debug)]
+        /// enum Example {
+        ///     A,
+        ///     B,
+        /// }
+        ///
+        /// // SAFETY: 1 is less than `Example::len()`
+        /// assert_eq!(unsafe { Example::from_ordinal_unchecked(1) }, Example::B);
+        /// ```
+        pub const unsafe fn from_ordinal_unchecked(ord: usize) -> Self {
+            debug_assert!(ord < #len, #invalid);
+            match Self::from_ordinal(ord) {
+                Some(value) => value,
+                // SAFETY: the caller guarantees that the ordinal is in range.
+                None => unsafe { ::core::hint::unreachable_unchecked() },
+            }
+        }
+    }
+}
+
+fn generate_discriminant_impl(
+    name: &syn::Ident,
+    unit_variants: &[&Variant],
+    other_variant: &Option<(&Variant, Type)>,
+    discriminant_type: &Type,
+    discriminants: &[Expr],
+) -> proc_macro2::TokenStream {
+    let scrutinee = self_scrutinee(other_variant);
+    let unit_match_arms = unit_variants.iter().zip(discriminants).map(|(variant, discriminant)| {
+        let variant_name = &variant.ident;
+        quote! { #name::#variant_name => #discriminant as #discriminant_type }
+    });
+
+    let other_arm = other_variant.as_ref().map(|(variant, other_type)| {
+        let variant_name = &variant.ident;
+        if is_wide_other(other_type, discriminant_type) {
+            quote! {
+                #name::#variant_name(val) => {
+                    let discr = *val as #discriminant_type;
+                    assert!(discr as #other_type == *val, "the \"other\" value does not fit in the repr");
+                    discr
+                }
+            }
+        } else {
+            quote! { #name::#variant_name(val) => *val }
+        }
+    });
+
+    quote! {
+        /// Returns the discriminant value of the enum variant.
+        ///
+        /// For "other" variants, returns the contained value. If the "other" field is wider than the
+        /// repr, this panics when the value does not fit; use `try_discriminant()` to check instead.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum)]
+        /// enum Example {
+        ///     A,      // 0
+        ///     B = 10, // 10
+        ///     C,      // 11
+        /// }
+        ///
+        /// assert_eq!(Example::A.discriminant(), 0);
+        /// assert_eq!(Example::B.discriminant(), 10);
+        /// assert_eq!(Example::C.discriminant(), 11);
+        /// ```
+         pub const fn discriminant(&self) -> #discriminant_type {
+            match #scrutinee {
+                #(#unit_match_arms,)*
+                #other_arm
+            }
+        }
+    }
+}
+
+fn generate_from_discriminant_impl(
+    name: &syn::Ident,
+    unit_variants: &[&Variant],
+    other_variant: &Option<(&Variant, Type)>,
+    fallback_variant: Option<&Variant>,
+    discriminant_type: &Type,
+    discriminants: &[Expr],
+) -> proc_macro2::TokenStream {
+    if let Some((other_variant, other_type)) = other_variant {
+        let match_arms = unit_variants.iter().zip(discriminants).map(|(variant, discriminant)| {
+            let variant_name = &variant.ident;
+            quote! { x if x == (#discriminant as #discriminant_type) => #name::#variant_name }
+        });
+
+        let other_name = &other_variant.ident;
+        let other_value = if is_wide_other(other_type, discriminant_type) {
+            quote! { other as #other_type }
+        } else {
+            quote! { other }
+        };
+        quote! {
+            /// Converts a discriminant value to an enum variant.
+            ///
+            /// For enums with an "other" variant, this will always return a value,
+            /// using the "other" variant for undefined discriminants.
+            ///
+            /// # Examples
+            ///
+            /// ```ignore
+            /// # use unit_enum::UnitEnum;
+            /// #[derive(UnitEnum, PartialEq, Debug)]
+            /// #[repr(u8)]
+            /// enum Example {
+            ///     A,      // 0
+            ///     B = 10, // 10
+            ///     #[unit_enum(other)]
+            ///     Other(u8),
+            /// }
+            ///
+            /// assert_eq!(Example::from_discriminant(0), Example::A);
+            /// assert_eq!(Example::from_discriminant(10), Example::B);
+            /// assert_eq!(Example::from_discriminant(42), Example::Other(42));
+            /// ```
+            pub const fn from_discriminant(discr: #discriminant_type) -> Self {
+                match discr {
+                    #(#match_arms,)*
+                    other => #name::#other_name(#other_value)
+                }
+            }
+        }
+    } else if let Some(fallback_variant) = fallback_variant {
+        let match_arms = unit_variants.iter().zip(discriminants).map(|(variant, discriminant)| {
+            let variant_name = &variant.ident;
+            quote! { x if x == (#discriminant as #discriminant_type) => #name::#variant_name }
+        });
+
+        let fallback_name = &fallback_variant.ident;
+        quote! {
+            /// Converts a discriminant value to an enum variant.
+            ///
+            /// For enums with a fallback variant, this will always return a value,
+            /// using the fallback variant for undefined discriminants.
+            ///
+            /// # Examples
+            ///
+            /// ```ignore
+            /// # use unit_enum::UnitEnum;
+            /// #[derive(UnitEnum, PartialEq, Debug)]
+            /// #[repr(u8)]
+            /// enum Example {
+            ///     A,      // 0
+            ///     B = 10, // 10
+            ///     #[unit_enum(fallback)]
+            ///     Unknown,
+            /// }
+            ///
+            /// assert_eq!(Example::from_discriminant(0), Example::A);
+            /// assert_eq!(Example::from_discriminant(11), Example::Unknown);
+            /// assert_eq!(Example::from_discriminant(42), Example::Unknown);
+            /// ```
+            pub const fn from_discriminant(discr: #discriminant_type) -> Self {
+                match discr {
+                    #(#match_arms,)*
+                    _ => #name::#fallback_name
+                }
+            }
+        }
+    } else {
+        let match_arms = unit_variants.iter().zip(discriminants).map(|(variant, discriminant)| {
+            let variant_name = &variant.ident;
+            quote! { x if x == (#discriminant as #discriminant_type) => Some(#name::#variant_name) }
+        });
+
+        quote! {
+            /// Converts a discriminant value to an enum variant, if possible.
+            ///
+            /// Returns `Some(variant)` if the discriminant corresponds to a defined variant,
+            /// or `None` if the discriminant is undefined.
+            ///
+            /// # Examples
+            ///
+            /// ```ignore
+            /// # use unit_enum::UnitEnum;
+            /// #[derive(UnitEnum, PartialEq, Debug)]
+            /// #[repr(u8)]
+            /// enum Example {
+            ///     A,      // 0
+            ///     B = 10, // 10
+            ///     C,      // 11
+            /// }
+            ///
+            /// assert_eq!(Example::from_discriminant(0), Some(Example::A));
+            /// assert_eq!(Example::from_discriminant(10), Some(Example::B));
+            /// assert_eq!(Example::from_discriminant(42), None);
+            /// ```
+            pub const fn from_discriminant(discr: #discriminant_type) -> Option<Self> {
+                match discr {
+                    #(#match_arms,)*
+                    _ => None
+                }
+            }
+        }
+    }
+}
+
+/// Conversions for an "other" field wider than the repr, which may hold values with no discriminant.
+fn generate_wide_other_impl(
+    name: &syn::Ident,
+    other_variant: &Variant,
+    other_type: &Type,
+    discriminant_type: &Type,
+) -> proc_macro2::TokenStream {
+    let other_name = &other_variant.ident;
+    quote! {
+        /// Returns the discriminant value of the enum variant,
+        /// or `None` if it is an "other" value that does not fit in the repr.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum)]
+        /// #[repr(u8)]
+        /// enum Example {
+        ///     A,
+        ///     #[unit_enum(other)]
+        ///     Other(u32),
+        /// }
+        ///
+        /// assert_eq!(Example::A.try_discriminant(), Some(0));
+        /// assert_eq!(Example::Other(42).try_discriminant(), Some(42));
+        /// assert_eq!(Example::Other(300).try_discriminant(), None);
+        /// ```
+        pub const fn try_discriminant(&self) -> Option<#discriminant_type> {
+            match self {
+                #name::#other_name(val) => {
+                    let discr = *val as #discriminant_type;
+                    if discr as #other_type == *val {
+                        Some(discr)
+                    } else {
+                        None
+                    }
+                }
+                _ => Some(self.discriminant()),
+            }
+        }
+
+        /// Converts a value of the "other" field type to an enum variant.
+        ///
+        /// Values that fit in the repr are converted like `from_discriminant()`,
+        /// and all others become the "other" variant.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum, PartialEq, Debug)]
+        /// #[repr(u8)]
+        /// enum Example {
+        ///     A,
+        ///     #[unit_enum(other)]
+        ///     Other(u32),
+        /// }
+        ///
+        /// assert_eq!(Example::from_wide_discriminant(0), Example::A);
+        /// assert_eq!(Example::from_wide_discriminant(300), Example::Other(300));
+        /// ```
+        pub const fn from_wide_discriminant(value: #other_type) -> Self {
+            let discr = value as #discriminant_type;
+            if discr as #other_type == value {
+                Self::from_discriminant(discr)
+            } else {
+                #name::#other_name(value)
+            }
+        }
+    }
+}
+
+fn generate_from_discriminant_unchecked_impl(
+    name: &syn::Ident,
+    is_plain_repr: bool,
+    discriminant_type: &Type,
+) -> proc_macro2::TokenStream {
+    let invalid = format!("invalid discriminant for {}", name);
+    let conversion = if is_plain_repr {
+        quote! {
+            // SAFETY: the enum is fieldless with a primitive repr, so its layout is exactly that of the
+            // repr type, and the caller guarantees that the value is a valid discriminant.
+            unsafe { ::core::mem::transmute::<#discriminant_type, Self>(discr) }
+        }
+    } else {
+        quote! {
+            match Self::from_discriminant(discr) {
+                Some(value) => value,
+                // SAFETY: the caller guarantees that the value is a valid discriminant.
+                None => unsafe { ::core::hint::unreachable_unchecked() },
+            }
+        }
+    };
+
+    quote! {
+        /// Converts a discriminant value to an enum variant without checking that it is defined.
+        ///
+        /// Debug builds still check the discriminant and panic if it is undefined. Not generated for enums
+        /// with an "other" or fallback variant, where `from_discriminant()` never fails.
+        ///
+        /// # Safety
+        ///
+        /// `discr` must be the discriminant of a unit variant, for example one accepted by `from_discriminant()`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum, PartialEq, Debug)]
+        /// #[repr(u8)]
+        /// enum Example {
+        ///     A,      // 0
+        ///     B = 10, // 10
+        /// }
+        ///
+        /// // SAFETY: 10 is the discriminant of `B`
+        /// assert_eq!(unsafe { Example::from_discriminant_unchecked(10) }, Example::B);
+        /// ```
+        pub const unsafe fn from_discriminant_unchecked(discr: #discriminant_type) -> Self {
+            debug_assert!(Self::ordinal_of_discriminant(discr).is_some(), #invalid);
+            #conversion
+        }
+    }
+}
+
+fn generate_from_discriminant_nearest_impl(
+    discriminant_type: &Type,
+    discriminants: &[Expr],
+) -> proc_macro2::TokenStream {
+    let num_variants = discriminants.len();
+
+    quote! {
+        /// Converts a discriminant value to the unit variant with the closest discriminant.
+        ///
+        /// On a tie, the variant with the lower discriminant is returned. The "other" variant is never returned.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum, PartialEq, Debug)]
+        /// #[repr(u8)]
+        /// enum Level {
+        ///     Low = 0,
+        ///     Mid = 128,
+        ///     High = 255,
+        /// }
+        ///
+        /// assert_eq!(Level::from_discriminant_nearest(100), Level::Mid);
+        /// assert_eq!(Level::from_discriminant_nearest(64), Level::Low); // tie
+        /// assert_eq!(Level::from_discriminant_nearest(255), Level::High);
+        /// ```
+        pub const fn from_discriminant_nearest(discr: #discriminant_type) -> Self {
+            const DISCRIMINANTS: [#discriminant_type; #num_variants] = [#(#discriminants as #discriminant_type),*];
+            let mut nearest = 0;
+            let mut ordinal = 1;
+            while ordinal < #num_variants {
+                let distance = discr.abs_diff(DISCRIMINANTS[ordinal]);
+                let nearest_distance = discr.abs_diff(DISCRIMINANTS[nearest]);
+                if distance < nearest_distance
+                    || (distance == nearest_distance && DISCRIMINANTS[ordinal] < DISCRIMINANTS[nearest]) {
+                    nearest = ordinal;
+                }
+                ordinal += 1;
+            }
+            match Self::from_ordinal(nearest) {
+                Some(value) => value,
+                None => unreachable!(),
+            }
+        }
+    }
+}
+
+fn generate_from_discriminant_saturating_impl(
+    infallible: bool,
+    discriminant_type: &Type,
+    discriminants: &[Expr],
+) -> proc_macro2::TokenStream {
+    let num_variants = discriminants.len();
+    let (return_type, from_ordinal) = if infallible {
+        (quote! { Self }, quote! {
+            match Self::from_ordinal(ordinal) {
+                Some(value) => value,
+                None => unreachable!(),
+            }
+        })
+    } else {
+        (quote! { Option<Self> }, quote! { Self::from_ordinal(ordinal) })
+    };
+
+    quote! {
+        /// Converts a discriminant value to an enum variant like `from_discriminant()`, but clamps values below
+        /// the lowest or above the highest discriminant to the unit variant with that discriminant.
+        ///
+        /// Undefined discriminants between the lowest and highest are handled like in `from_discriminant()`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum, PartialEq, Debug)]
+        /// #[repr(i8)]
+        /// enum Gain {
+        ///     Mute = -2,
+        ///     Quiet,
+        ///     Normal,
+        ///     Loud,
+        /// }
+        ///
+        /// assert_eq!(Gain::from_discriminant_saturating(0), Some(Gain::Normal));
+        /// assert_eq!(Gain::from_discriminant_saturating(-100), Some(Gain::Mute));
+        /// assert_eq!(Gain::from_discriminant_saturating(100), Some(Gain::Loud));
+        /// ```
+        pub const fn from_discriminant_saturating(discr: #discriminant_type) -> #return_type {
+            const DISCRIMINANTS: [#discriminant_type; #num_variants] = [#(#discriminants as #discriminant_type),*];
+            let (mut lowest, mut highest) = (0, 0);
+            let mut ordinal = 1;
+            while ordinal < #num_variants {
+                if DISCRIMINANTS[ordinal] < DISCRIMINANTS[lowest] {
+                    lowest = ordinal;
+                }
+                if DISCRIMINANTS[ordinal] > DISCRIMINANTS[highest] {
+                    highest = ordinal;
+                }
+                ordinal += 1;
+            }
+            let ordinal = if discr < DISCRIMINANTS[lowest] {
+                lowest
+            } else if discr > DISCRIMINANTS[highest] {
+                highest
+            } else {
+                return Self::from_discriminant(discr);
+            };
+            #from_ordinal
+        }
+    }
+}
+
+fn generate_steps_between_impl(num_variants: usize) -> proc_macro2::TokenStream {
+    quote! {
+        /// Returns the number of steps from `start` to `end` in declaration order.
+        ///
+        /// Like `Step::steps_between` in the standard library, returns `None` if `end` comes before `start`,
+        /// so swap the arguments to count backwards. Also returns `None` if either is the "other" variant.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum)]
+        /// enum Example {
+        ///     A,
+        ///     B = 10,
+        ///     C,
+        /// }
+        ///
+        /// assert_eq!(Example::steps_between(&Example::A, &Example::C), Some(2));
+        /// assert_eq!(Example::steps_between(&Example::B, &Example::B), Some(0));
+        /// assert_eq!(Example::steps_between(&Example::C, &Example::A), None);
+        /// ```
+        pub const fn steps_between(start: &Self, end: &Self) -> Option<usize> {
+            let (start, end) = (start.ordinal(), end.ordinal());
+            if start <= end && end < #num_variants {
+                Some(end - start)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+fn generate_ordinal_discriminant_impl(
+    discriminant_type: &Type,
+    discriminants: &[Expr],
+) -> proc_macro2::TokenStream {
+    let discriminant_arms = discriminants.iter().enumerate().map(|(index, discriminant)| {
+        quote! { #index => Some(#discriminant as #discriminant_type) }
+    });
+    let ordinal_arms = discriminants.iter().enumerate().map(|(index, discriminant)| {
+        quote! { x if x == (#discriminant as #discriminant_type) => Some(#index) }
+    });
+
+    quote! {
+        /// Returns the discriminant of the unit variant at the given ordinal, without constructing it.
+        ///
+        /// Returns `None` if the ordinal is out of range or would correspond to the "other" variant.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum)]
+        /// enum Example {
+        ///     A,      // ordinal: 0
+        ///     B = 10, // ordinal: 1
+        /// }
+        ///
+        /// assert_eq!(Example::discriminant_of_ordinal(1), Some(10));
+        /// assert_eq!(Example::discriminant_of_ordinal(2), None);
+        /// ```
+        pub const fn discriminant_of_ordinal(ord: usize) -> Option<#discriminant_type> {
+            match ord {
+                #(#discriminant_arms,)*
+                _ => None
+            }
+        }
+
+        /// Returns the ordinal of the unit variant with the given discriminant, without constructing it.
+        ///
+        /// Returns `None` for undefined discriminants, including those held by the "other" variant.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum)]
+        /// enum Example {
+        ///     A,      // ordinal: 0
+        ///     B = 10, // ordinal: 1
+        /// }
+        ///
+        /// assert_eq!(Example::ordinal_of_discriminant(10), Some(1));
+        /// assert_eq!(Example::ordinal_of_discriminant(1), None);
+        /// ```
+        pub const fn ordinal_of_discriminant(discr: #discriminant_type) -> Option<usize> {
+            match discr {
+                #(#ordinal_arms,)*
+                _ => None
+            }
+        }
+    }
+}
+
+fn generate_char_impl(infallible: bool) -> proc_macro2::TokenStream {
+    let from_discriminant = if infallible {
+        quote! { Some(Self::from_discriminant(c as u8)) }
+    } else {
+        quote! { Self::from_discriminant(c as u8) }
+    };
+
+    quote! {
+        /// Returns the discriminant of the variant as a `char`, like `discriminant() as char`.
+        ///
+        /// Only generated for `#[repr(u8)]` enums whose discriminants are all byte literals.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum)]
+        /// #[repr(u8)]
+        /// enum Side {
+        ///     Buy = b'1',
+        ///     Sell = b'2',
+        /// }
+        ///
+        /// assert_eq!(Side::Sell.as_char(), '2');
+        /// ```
+        pub const fn as_char(&self) -> char {
+            self.discriminant() as char
+        }
+
+        /// Converts a `char` to the variant with that discriminant, like `from_discriminant()`.
+        ///
+        /// Returns `None` for characters above U+00FF, which don't fit in a byte, and for undefined
+        /// discriminants unless there is an "other" or fallback variant.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum, PartialEq, Debug)]
+        /// #[repr(u8)]
+        /// enum Side {
+        ///     Buy = b'1',
+        ///     Sell = b'2',
+        /// }
+        ///
+        /// assert_eq!(Side::from_char('1'), Some(Side::Buy));
+        /// assert_eq!(Side::from_char('3'), None);
+        /// ```
+        pub const fn from_char(c: char) -> Option<Self> {
+            if c as u32 > u8::MAX as u32 {
+                return None;
+            }
+            #from_discriminant
+        }
+    }
+}
+
+fn generate_values_impl(
+    name: &syn::Ident,
+    vis: &syn::Visibility,
+    len: usize,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    // Named after the method rather than `{Enum}Iter`, which strum's EnumIter derive defines
+    let iter = format_ident!("{}Values", name);
+    let iter_doc = format!("An iterator over the variants of [`{}`], returned by [`{}::values()`].", name, name);
+
+    let values_impl = quote! {
+        /// Returns an iterator over all unit variants of the enum.
+        ///
+        /// Note: This does not include values from the "other" variant, if present, unless it is marked with
+        /// `#[unit_enum(other(listed = ...))]`, which makes it yield that value last.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum, PartialEq, Debug)]
+        /// enum Example {
+        ///     A,
+        ///     B,
+        ///     #[unit_enum(other)]
+        ///     Other(i32),
+        /// }
+        ///
+        /// let values: Vec<_> = Example::values().collect();
+        /// assert_eq!(values, vec![Example::A, Example::B]);
+        /// ```
+        pub fn values() -> #iter {
+            #iter { front: 0, back: #len }
+        }
+    };
+
+    let iter_type = quote! {
+        #[doc = #iter_doc]
+        #[derive(Debug, Clone)]
+        #vis struct #iter {
+            front: usize,
+            back: usize,
+        }
+
+        impl #iter {
+            fn value_at(index: usize) -> Option<#name> {
+                // Also yields a listed "other" value, right after the unit variants
+                #name::from_ordinal(index)
+            }
+        }
+
+        impl ::core::iter::Iterator for #iter {
+            type Item = #name;
+
+            fn next(&mut self) -> Option<#name> {
+                if self.front == self.back {
+                    return None;
+                }
+                self.front += 1;
+                #iter::value_at(self.front - 1)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.back - self.front;
+                (len, Some(len))
+            }
+
+            fn nth(&mut self, n: usize) -> Option<#name> {
+                self.front = self.front.saturating_add(n).min(self.back);
+                self.next()
+            }
+        }
+
+        impl ::core::iter::DoubleEndedIterator for #iter {
+            fn next_back(&mut self) -> Option<#name> {
+                if self.front == self.back {
+                    return None;
+                }
+                self.back -= 1;
+                #iter::value_at(self.back)
+            }
+        }
+
+        impl ::core::iter::ExactSizeIterator for #iter {}
+
+        impl ::core::iter::FusedIterator for #iter {}
+    };
+
+    (values_impl, iter_type)
+}
+
+fn generate_is_variant_impl(
+    name: &syn::Ident,
+    unit_variants: &[&Variant],
+    other_variant: &Option<(&Variant, Type)>,
+) -> proc_macro2::TokenStream {
+    let variants = unit_variants.iter().map(|variant| (&variant.ident, quote! {}))
+        .chain(other_variant.as_ref().map(|(variant, _)| (&variant.ident, quote! { (_) })));
+    let predicates = variants.map(|(variant_name, fields)| {
+        let predicate = format_ident!("is_{}", StrumCase::Snake.apply(&variant_name.unraw().to_string()));
+        let doc = format!("Returns `true` if this is [`{}::{}`].", name, variant_name);
+        quote! {
+            #[doc = #doc]
+            pub const fn #predicate(&self) -> bool {
+                matches!(self, #name::#variant_name #fields)
+            }
+        }
+    });
+
+    quote! {
+        #(#predicates)*
+    }
+}
+
+/// Generates `as_known()` and the `{Enum}Known` companion enum holding only the unit variants.
+fn generate_known_impl(
+    name: &syn::Ident,
+    vis: &syn::Visibility,
+    unit_variants: &[&Variant],
+    other_variant: &Variant,
+    discriminant_type: &Type,
+    discriminants: &[Expr],
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let known = format_ident!("{}Known", name);
+    let other_name = &other_variant.ident;
+    let known_doc = format!("The unit variants of [`{}`], without the \"other\" variant, for exhaustive matches.", name);
+    // Enums without variants can't have a repr
+    let repr = (!unit_variants.is_empty()).then(|| quote! { #[repr(#discriminant_type)] });
+    let known_variants = unit_variants.iter().zip(discriminants).map(|(variant, discriminant)| {
+        let variant_name = &variant.ident;
+        let docs = variant.attrs.iter().filter(|attr| attr.path().is_ident("doc"));
+        quote! {
+            #(#docs)*
+            #variant_name = #discriminant as #discriminant_type
+        }
+    });
+    let to_known_arms = unit_variants.iter().map(|variant| {
+        let variant_name = &variant.ident;
+        quote! { #name::#variant_name => Some(#known::#variant_name) }
+    });
+    let from_known_arms = unit_variants.iter().map(|variant| {
+        let variant_name = &variant.ident;
+        quote! { #known::#variant_name => #name::#variant_name }
+    });
+
+    let as_known_impl = quote! {
+        /// Returns the variant as its counterpart in the known-subset companion enum,
+        /// or `None` for the "other" variant.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum)]
+        /// #[unit_enum(known)]
+        /// enum Example {
+        ///     A,
+        ///     #[unit_enum(other)]
+        ///     Other(i32),
+        /// }
+        ///
+        /// assert_eq!(Example::A.as_known(), Some(ExampleKnown::A));
+        /// assert_eq!(Example::Other(7).as_known(), None);
+        /// ```
+        pub const fn as_known(&self) -> Option<#known> {
+            match self {
+                #(#to_known_arms,)*
+                #name::#other_name(_) => None,
+            }
+        }
+    };
+
+    let known_enum = quote! {
+        #[doc = #known_doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #repr
+        #vis enum #known {
+            #(#known_variants,)*
+        }
+
+        impl ::core::convert::From<#known> for #name {
+            fn from(value: #known) -> Self {
+                match value {
+                    #(#from_known_arms,)*
+                }
+            }
+        }
+    };
+
+    (as_known_impl, known_enum)
+}
+
+/// The methods of the `{Enum}Counts` type, which a per-variant count getter must not shadow.
+const COUNTS_METHODS: &[&str] = &["new", "record", "get", "total", "iter", "merge"];
+
+/// The name of the count getter of a variant in `{Enum}Counts`, raw if it is a keyword.
+fn count_getter(variant_name: &syn::Ident) -> Result<syn::Ident, Error> {
+    let getter = StrumCase::Snake.apply(&variant_name.unraw().to_string());
+    if COUNTS_METHODS.contains(&getter.as_str()) || ["self", "super", "crate"].contains(&getter.as_str()) {
+        return Err(Error::new_spanned(variant_name, format!(
+            "`{}` can't be used as a count getter with #[unit_enum(counts)], as it clashes with `{}()`",
+            variant_name, getter)));
+    }
+    Ok(syn::parse_str(&getter).unwrap_or_else(|_| syn::Ident::new_raw(&getter, variant_name.span())))
+}
+
+/// Generates the `{Enum}Counts` type tallying values by variant, with one slot per ordinal.
+fn generate_counts_type(
+    name: &syn::Ident,
+    vis: &syn::Visibility,
+    unit_variants: &[&Variant],
+    other_variant: &Option<(&Variant, Type)>,
+) -> proc_macro2::TokenStream {
+    let counts = format_ident!("{}Counts", name);
+    let num_slots = unit_variants.len() + usize::from(other_variant.is_some());
+    let counts_doc = format!("Counts of [`{}`] values by variant, with one `u64` per variant.", name);
+    let getters = unit_variants.iter().copied().chain(other_variant.as_ref().map(|(variant, _)| *variant))
+        .enumerate()
+        .map(|(index, variant)| {
+            let getter = count_getter(&variant.ident).expect("count getters are checked during validation");
+            let doc = format!("Returns the number of [`{}::{}`] values recorded.", name, variant.ident);
+            quote! {
+                #[doc = #doc]
+                pub const fn #getter(&self) -> u64 {
+                    self.counts[#index]
+                }
+            }
+        });
+
+    quote! {
+        #[doc = #counts_doc]
+        ///
+        /// All "other" values are counted together.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #vis struct #counts {
+            counts: [u64; #num_slots],
+        }
+
+        impl #counts {
+            /// Returns counts with every variant at zero.
+            pub const fn new() -> Self {
+                Self { counts: [0; #num_slots] }
+            }
+
+            /// Counts one occurrence of `value`.
+            pub fn record(&mut self, value: #name) {
+                self.counts[value.ordinal()] += 1;
+            }
+
+            /// Returns the number of values recorded with the same variant as `value`.
+            pub const fn get(&self, value: #name) -> u64 {
+                self.counts[value.ordinal()]
+            }
+
+            /// Returns the number of values recorded, over all variants.
+            pub fn total(&self) -> u64 {
+                self.counts.iter().sum()
+            }
+
+            /// Iterates over the variants in `values()` order, with their counts.
+            pub fn iter(&self) -> impl Iterator<Item = (#name, u64)> {
+                #name::values().zip(self.counts)
+            }
+
+            /// Adds the counts of `other` to these.
+            pub fn merge(&mut self, other: &Self) {
+                for (count, other_count) in self.counts.iter_mut().zip(other.counts) {
+                    *count += other_count;
+                }
+            }
+
+            #(#getters)*
+        }
+
+        impl ::core::default::Default for #counts {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl ::core::iter::Extend<#name> for #counts {
+            fn extend<__I: ::core::iter::IntoIterator<Item = #name>>(&mut self, iter: __I) {
+                for value in iter {
+                    self.record(value);
+                }
+            }
+        }
+
+        impl ::core::iter::FromIterator<#name> for #counts {
+            fn from_iter<__I: ::core::iter::IntoIterator<Item = #name>>(iter: __I) -> Self {
+                let mut counts = Self::new();
+                counts.extend(iter);
+                counts
+            }
+        }
+    }
+}
+
+/// Converts to and from an enum with the same variant names, so a variant missing on either side fails to compile.
+fn generate_maps_to_name_impl(
+    name: &syn::Ident,
+    unit_variants: &[&Variant],
+    other_variant: &Option<(&Variant, Type)>,
+    target: &syn::Path,
+) -> proc_macro2::TokenStream {
+    let variants = unit_variants.iter().map(|variant| (&variant.ident, quote! {}))
+        .chain(other_variant.as_ref().map(|(variant, _)| (&variant.ident, quote! { (value) })));
+    let (to_target_arms, from_target_arms): (Vec<_>, Vec<_>) = variants.map(|(variant_name, fields)| {
+        (
+            quote_spanned! {variant_name.span()=> #name::#variant_name #fields => #target::#variant_name #fields },
+            quote_spanned! {variant_name.span()=> #target::#variant_name #fields => #name::#variant_name #fields },
+        )
+    }).unzip();
+
+    quote! {
+        impl ::core::convert::From<#name> for #target {
+            fn from(value: #name) -> Self {
+                match value {
+                    #(#to_target_arms,)*
+                }
+            }
+        }
+
+        impl ::core::convert::From<#target> for #name {
+            fn from(value: #target) -> Self {
+                match value {
+                    #(#from_target_arms,)*
+                }
+            }
+        }
+    }
+}
+
+/// Converts to and from another `UnitEnum` with the same repr by discriminant, checking at compile time that
+/// the unit variants of both enums correspond.
+fn generate_maps_to_discriminant_impl(
+    name: &syn::Ident,
+    unit_variants: &[&Variant],
+    target: &syn::Path,
+    discriminant_type: &Type,
+    discriminants: &[Expr],
+) -> proc_macro2::TokenStream {
+    let target_str = target.to_token_stream().to_string().replace(' ', "");
+    let forward_checks = unit_variants.iter().zip(discriminants).map(|(variant, discriminant)| {
+        let variant_name = &variant.ident;
+        let missing = format!("{} has no variant with the discriminant of {}::{}", target_str, name, variant_name);
+        // Only the assertion points at the variant, so lints on the cast stay attributed to the macro
+        let check = quote_spanned! {variant_name.span()=>
+            assert!(#target::ordinal_of_discriminant(discr).is_some(), #missing);
+        };
+        quote! {
+            {
+                let discr = #discriminant as #discriminant_type;
+                #check
+            }
+        }
+    });
+    let unmatched = format!("{} has a variant with a discriminant that {} lacks", target_str, name);
+    // Between enums without variants, the conversion can't fail because there is nothing to convert
+    let allow_infallible = unit_variants.is_empty().then(|| quote! { #[allow(clippy::infallible_try_from)] });
+
+    quote! {
+        const _: () = {
+            #(#forward_checks)*
+            let mut ordinal = 0;
+            while let Some(discr) = #target::discriminant_of_ordinal(ordinal) {
+                assert!(#name::ordinal_of_discriminant(discr).is_some(), #unmatched);
+                ordinal += 1;
+            }
+        };
+
+        impl ::core::convert::From<#name> for #target {
+            fn from(value: #name) -> Self {
+                match #target::ordinal_of_discriminant(value.discriminant()).and_then(#target::from_ordinal) {
+                    Some(mapped) => mapped,
+                    None => unreachable!("checked at compile time"),
+                }
+            }
+        }
+
+        // The target may have an "other" variant, which has no counterpart
+        #allow_infallible
+        impl ::core::convert::TryFrom<#target> for #name {
+            type Error = #target;
+
+            fn try_from(value: #target) -> ::core::result::Result<Self, Self::Error> {
+                match #name::ordinal_of_discriminant(value.discriminant()).and_then(#name::from_ordinal) {
+                    Some(mapped) => Ok(mapped),
+                    None => Err(value),
+                }
+            }
+        }
+    }
+}
+
+fn generate_values_with_discriminants_impl(
+    name: &syn::Ident,
+    unit_variants: &[&Variant],
+    discriminant_type: &Type,
+    discriminants: &[Expr],
+) -> proc_macro2::TokenStream {
+    let pairs = unit_variants.iter().zip(discriminants).map(|(variant, discriminant)| {
+        let variant_name = &variant.ident;
+        quote! { (#name::#variant_name, #discriminant as #discriminant_type) }
+    });
+    let num_variants = unit_variants.len();
+
+    quote! {
+        /// Returns an iterator over all unit variants of the enum paired with their discriminants.
+        ///
+        /// Note: This does not include values from the "other" variant, if present.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum, PartialEq, Debug)]
+        /// enum Example {
+        ///     A,
+        ///     B = 10,
+        /// }
+        ///
+        /// let pairs: Vec<_> = Example::values_with_discriminants().collect();
+        /// assert_eq!(pairs, vec![(Example::A, 0), (Example::B, 10)]);
+        /// ```
+        pub fn values_with_discriminants() -> impl Iterator<Item = (Self, #discriminant_type)> {
+            let pairs: [(Self, #discriminant_type); #num_variants] = [#(#pairs),*];
+            ::core::iter::IntoIterator::into_iter(pairs)
+        }
+    }
+}
+
+fn generate_enumerate_values_impl() -> proc_macro2::TokenStream {
+    quote! {
+        /// Returns an iterator over all variants yielded by `values()`, each paired with its `ordinal()`.
+        ///
+        /// Note: A listed "other" variant is yielded last with the last ordinal, like `ordinal()` returns for it.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum, PartialEq, Debug)]
+        /// enum Example {
+        ///     A = 5,
+        ///     B = 10,
+        /// }
+        ///
+        /// let pairs: Vec<_> = Example::enumerate_values().collect();
+        /// assert_eq!(pairs, vec![(0, Example::A), (1, Example::B)]);
+        /// ```
+        pub fn enumerate_values() -> impl DoubleEndedIterator<Item = (usize, Self)> + ExactSizeIterator {
+            Self::values().map(|value| (value.ordinal(), value))
+        }
+    }
+}
+
+fn generate_iter_names_impl(unit_names: &[LitStr], listed_other_name: Option<&LitStr>) -> proc_macro2::TokenStream {
+    let names = unit_names.iter().chain(listed_other_name);
+    quote! {
+        /// Returns an iterator over the names of all unit variants, in declaration order.
+        ///
+        /// The names are the same as returned by `name()`. The "other" variant is not included, unless it is
+        /// marked with `#[unit_enum(other(listed = ...))]`.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum)]
+        /// enum Example {
+        ///     A,
+        ///     B,
+        ///     #[unit_enum(other)]
+        ///     Other(i32),
+        /// }
+        ///
+        /// let names: Vec<_> = Example::iter_names().collect();
+        /// assert_eq!(names, vec!["A", "B"]);
+        /// ```
+        pub fn iter_names() -> impl Iterator<Item = &'static str> {
+            ::core::iter::IntoIterator::into_iter([#(#names),*])
+        }
+    }
+}
+
+fn generate_values_sorted_impl(
+    discriminant_type: &Type,
+    discriminants: &[Expr],
+) -> proc_macro2::TokenStream {
+    let num_variants = discriminants.len();
+
+    quote! {
+        /// Returns an iterator over all unit variants of the enum, ordered by discriminant.
+        ///
+        /// The order is computed at compile time. Like `values()`, this does not include the "other" variant.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum, PartialEq, Debug)]
+        /// enum Example {
+        ///     A = 20,
+        ///     B = 10,
+        ///     C,      // 11
+        /// }
+        ///
+        /// let values: Vec<_> = Example::values_sorted().collect();
+        /// assert_eq!(values, vec![Example::B, Example::C, Example::A]);
+        /// ```
+        pub fn values_sorted() -> impl Iterator<Item = Self> {
+            // Insertion sort, which is linear for the usual case of discriminants declared in order
+            const ORDINALS: [usize; #num_variants] = {
+                let discriminants: [#discriminant_type; #num_variants] = [#(#discriminants as #discriminant_type),*];
+                let mut ordinals = [0; #num_variants];
+                let mut i = 0;
+                while i < #num_variants {
+                    ordinals[i] = i;
+                    let mut j = i;
+                    while j > 0 && discriminants[ordinals[j - 1]] > discriminants[ordinals[j]] {
+                        let swapped = ordinals[j];
+                        ordinals[j] = ordinals[j - 1];
+                        ordinals[j - 1] = swapped;
+                        j -= 1;
+                    }
+                    i += 1;
+                }
+                ordinals
+            };
+
+            ::core::iter::IntoIterator::into_iter(ORDINALS).filter_map(Self::from_ordinal)
+        }
+    }
+}
+
+fn generate_try_from_bytes_impl(discriminant_type: &Type) -> proc_macro2::TokenStream {
+    quote! {
+        /// Reinterprets a byte slice as a reference to an enum value, if possible.
+        ///
+        /// Returns `Some(&variant)` if the slice is exactly `size_of::<Self>()` bytes long,
+        /// suitably aligned, and holds (in native byte order) the discriminant of a defined variant.
+        /// Returns `None` otherwise, so an invalid value is never produced.
+        ///
+        /// Only generated for enums with an explicit `#[repr(type)]` and no "other" variant.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum, PartialEq, Debug)]
+        /// #[repr(u8)]
+        /// enum Example {
+        ///     A = 1,
+        ///     B = 2,
+        /// }
+        ///
+        /// assert_eq!(Example::try_from_bytes(&[2]), Some(&Example::B));
+        /// assert_eq!(Example::try_from_bytes(&[3]), None);    // Undefined discriminant
+        /// assert_eq!(Example::try_from_bytes(&[1, 2]), None); // Wrong length
+        /// ```
+        pub fn try_from_bytes(bytes: &[u8]) -> Option<&Self> {
+            let ptr = bytes.as_ptr().cast::<Self>();
+            if bytes.len() != ::core::mem::size_of::<Self>() || !ptr.is_aligned() {
+                return None;
+            }
+
+            let mut raw = [0u8; ::core::mem::size_of::<#discriminant_type>()];
+            raw.copy_from_slice(bytes);
+            Self::ordinal_of_discriminant(<#discriminant_type>::from_ne_bytes(raw))?;
+
+            // SAFETY: the enum is fieldless with a primitive repr, so its layout is exactly
+            // that of the repr type. The pointer is aligned, covers the whole value, borrows
+            // from `bytes`, and the bits were just checked to be a valid discriminant.
+            Some(unsafe { &*ptr })
+        }
+    }
+}
+
+fn generate_bytemuck_impl(name: &syn::Ident, discriminant_type: &Type) -> proc_macro2::TokenStream {
+    quote! {
+        // SAFETY: the enum is fieldless with a primitive repr, so every value is a fully
+        // initialized integer of the repr type and a bit pattern is valid exactly when
+        // it is the discriminant of a defined variant.
+        unsafe impl ::bytemuck::CheckedBitPattern for #name {
+            type Bits = #discriminant_type;
+
+            fn is_valid_bit_pattern(bits: &Self::Bits) -> bool {
+                Self::ordinal_of_discriminant(*bits).is_some()
+            }
+        }
+
+        // SAFETY: fieldless enums with a primitive repr have no padding bytes.
+        unsafe impl ::bytemuck::NoUninit for #name {}
+    }
+}
+
+fn generate_enum_map_impl(name: &syn::Ident, num_variants: usize) -> proc_macro2::TokenStream {
+    quote! {
+        impl ::enum_map::Enum for #name {
+            const LENGTH: usize = #num_variants;
+
+            fn from_usize(value: usize) -> Self {
+                match Self::from_ordinal(value) {
+                    Some(value) => value,
+                    None => panic!("index out of range for an enum with {} variants", #num_variants),
+                }
+            }
+
+            fn into_usize(self) -> usize {
+                self.ordinal()
+            }
+        }
+
+        impl<V> ::enum_map::EnumArray<V> for #name {
+            type Array = [V; #num_variants];
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn generate_serde_impl(
+    name: &syn::Ident,
+    unit_names: &[LitStr],
+    other_variant: &Option<(&Variant, Type)>,
+    infallible: bool,
+    num_variants: usize,
+    discriminant_type: &Type,
+    serde_mode: SerdeMode,
+    deserialize_any: bool,
+) -> proc_macro2::TokenStream {
+    let by_name = || {
+        generate_serde_name_impl(name, unit_names, other_variant, infallible, discriminant_type, deserialize_any)
+    };
+    let by_discriminant =
+        || generate_serde_discriminant_impl(name, other_variant, infallible, num_variants, discriminant_type);
+    let (serialize, deserialize) = match serde_mode {
+        SerdeMode::Name => by_name(),
+        // Human-readable formats also take names, through the name visitor, which accepts discriminants too
+        SerdeMode::Discriminant if deserialize_any => {
+            let ((_, name_deserialize), (discriminant_serialize, discriminant_deserialize)) =
+                (by_name(), by_discriminant());
+            (
+                discriminant_serialize,
+                quote! {
+                    if deserializer.is_human_readable() {
+                        #name_deserialize
+                    } else {
+                        #discriminant_deserialize
+                    }
+                },
+            )
+        }
+        SerdeMode::Discriminant => by_discriminant(),
+        SerdeMode::Ordinal => generate_serde_ordinal_impl(name, num_variants),
+        SerdeMode::Auto => {
+            let ((name_serialize, name_deserialize), (discriminant_serialize, discriminant_deserialize)) =
+                (by_name(), by_discriminant());
+            (
+                quote! {
+                    if serializer.is_human_readable() {
+                        #name_serialize
+                    } else {
+                        #discriminant_serialize
+                    }
+                },
+                quote! {
+                    if deserializer.is_human_readable() {
+                        #name_deserialize
+                    } else {
+                        #discriminant_deserialize
+                    }
+                },
+            )
+        }
+    };
+
+    quote! {
+        impl ::serde::Serialize for #name {
+            fn serialize<__S>(&self, serializer: __S) -> ::core::result::Result<__S::Ok, __S::Error>
+            where
+                __S: ::serde::Serializer,
+            {
+                #serialize
+            }
+        }
+
+        impl<'de> ::serde::Deserialize<'de> for #name {
+            fn deserialize<__D>(deserializer: __D) -> ::core::result::Result<Self, __D::Error>
+            where
+                __D: ::serde::Deserializer<'de>,
+            {
+                #deserialize
+            }
+        }
+    }
+}
+
+/// The bodies of `serialize()` and `deserialize()` representing variants by name.
+fn generate_serde_name_impl(
+    name: &syn::Ident,
+    unit_names: &[LitStr],
+    other_variant: &Option<(&Variant, Type)>,
+    infallible: bool,
+    discriminant_type: &Type,
+    deserialize_any: bool,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+
+    // Names cannot carry the payload, so the "other" variant falls back to its raw value. Formats that aren't
+    // self-describing can't tell the two apart when reading them back, so they get an enum tagging which one it is
+    let enum_name = name.unraw().to_string();
+    let other_form = other_variant.as_ref().map(|(variant, _)| variant.ident.unraw().to_string());
+    let other_arms = other_variant.as_ref().map(|(variant, _)| {
+        let variant_name = &variant.ident;
+        quote! {
+            #name::#variant_name(val) if !serializer.is_human_readable() => {
+                serializer.serialize_newtype_variant(#enum_name, 1, #other_form, val)
+            }
+            #name::#variant_name(val) => ::serde::Serialize::serialize(val, serializer),
+            _ if !serializer.is_human_readable() => serializer.serialize_newtype_variant(#enum_name, 0, "name", self.name()),
+        }
+    });
+
+    // ...and numeric codes are accepted back into it, like they are with `deserialize_any` even without an "other"
+    // variant. Telling names and numbers apart needs a self-describing format
+    let from_number = |number_type: proc_macro2::TokenStream, unexpected: proc_macro2::TokenStream| {
+        let invalid = quote! { __E::invalid_value(::serde::de::Unexpected::#unexpected(value), &self) };
+        match other_variant {
+            Some((_, other_type)) => {
+                let from_other = other_value_constructor(name, other_type, discriminant_type);
+                quote! {
+                    <#other_type as ::core::convert::TryFrom<#number_type>>::try_from(value)
+                        .map(#from_other)
+                        .map_err(|_| #invalid)
+                }
+            }
+            None if infallible => quote! {
+                <#discriminant_type as ::core::convert::TryFrom<#number_type>>::try_from(value)
+                    .map(#name::from_discriminant)
+                    .map_err(|_| #invalid)
+            },
+            None => quote! {
+                <#discriminant_type as ::core::convert::TryFrom<#number_type>>::try_from(value).ok()
+                    .and_then(#name::from_discriminant)
+                    .ok_or_else(|| #invalid)
+            },
+        }
+    };
+    let (expecting, visit_numbers, deserialize_call) = if let Some((_, other_type)) = other_variant {
+        let from_unsigned = from_number(quote! { u64 }, quote! { Unsigned });
+        let from_signed = from_number(quote! { i64 }, quote! { Signed });
+        let from_other = other_value_constructor(name, other_type, discriminant_type);
+        let expecting = format!("a variant name or discriminant of {}", name);
+        (
+            expecting.clone(),
+            quote! {
+                fn visit_u64<__E>(self, value: u64) -> ::core::result::Result<Self::Value, __E>
+                where
+                    __E: ::serde::de::Error,
+                {
+                    #from_unsigned
+                }
+
+                fn visit_i64<__E>(self, value: i64) -> ::core::result::Result<Self::Value, __E>
+                where
+                    __E: ::serde::de::Error,
+                {
+                    #from_signed
+                }
+            },
+            quote! {
+                if deserializer.is_human_readable() {
+                    return deserializer.deserialize_any(__Visitor);
+                }
+
+                const FORMS: &[&str] = &["name", #other_form];
+
+                enum __Form {
+                    Name,
+                    Other,
+                }
+
+                impl<'de> ::serde::Deserialize<'de> for __Form {
+                    fn deserialize<__D>(deserializer: __D) -> ::core::result::Result<Self, __D::Error>
+                    where
+                        __D: ::serde::Deserializer<'de>,
+                    {
+                        struct __FormVisitor;
+
+                        impl<'de> ::serde::de::Visitor<'de> for __FormVisitor {
+                            type Value = __Form;
+
+                            fn expecting(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                                formatter.write_str("`name` or the \"other\" variant")
+                            }
+
+                            fn visit_u64<__E>(self, value: u64) -> ::core::result::Result<Self::Value, __E>
+                            where
+                                __E: ::serde::de::Error,
+                            {
+                                match value {
+                                    0 => Ok(__Form::Name),
+                                    1 => Ok(__Form::Other),
+                                    _ => Err(__E::invalid_value(::serde::de::Unexpected::Unsigned(value), &self)),
+                                }
+                            }
+
+                            fn visit_str<__E>(self, value: &str) -> ::core::result::Result<Self::Value, __E>
+                            where
+                                __E: ::serde::de::Error,
+                            {
+                                match value {
+                                    "name" => Ok(__Form::Name),
+                                    #other_form => Ok(__Form::Other),
+                                    _ => Err(__E::unknown_variant(value, FORMS)),
+                                }
+                            }
+                        }
+
+                        deserializer.deserialize_identifier(__FormVisitor)
+                    }
+                }
+
+                struct __Name;
+
+                impl<'de> ::serde::de::DeserializeSeed<'de> for __Name {
+                    type Value = #name;
+
+                    fn deserialize<__D>(self, deserializer: __D) -> ::core::result::Result<Self::Value, __D::Error>
+                    where
+                        __D: ::serde::Deserializer<'de>,
+                    {
+                        deserializer.deserialize_str(__Visitor)
+                    }
+                }
+
+                struct __FormsVisitor;
+
+                impl<'de> ::serde::de::Visitor<'de> for __FormsVisitor {
+                    type Value = #name;
+
+                    fn expecting(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        formatter.write_str(#expecting)
+                    }
+
+                    fn visit_enum<__A>(self, data: __A) -> ::core::result::Result<Self::Value, __A::Error>
+                    where
+                        __A: ::serde::de::EnumAccess<'de>,
+                    {
+                        match ::serde::de::EnumAccess::variant(data)? {
+                            (__Form::Name, variant) => ::serde::de::VariantAccess::newtype_variant_seed(variant, __Name),
+                            (__Form::Other, variant) => {
+                                ::serde::de::VariantAccess::newtype_variant::<#other_type>(variant).map(#from_other)
+                            }
+                        }
+                    }
+                }
+
+                deserializer.deserialize_enum(#enum_name, FORMS, __FormsVisitor)
+            },
+        )
+    } else if deserialize_any {
+        let from_unsigned = from_number(quote! { u64 }, quote! { Unsigned });
+        let from_signed = from_number(quote! { i64 }, quote! { Signed });
+        (
+            format!("a variant name or discriminant of {}", name),
+            quote! {
+                fn visit_u64<__E>(self, value: u64) -> ::core::result::Result<Self::Value, __E>
+                where
+                    __E: ::serde::de::Error,
+                {
+                    #from_unsigned
+                }
+
+                fn visit_i64<__E>(self, value: i64) -> ::core::result::Result<Self::Value, __E>
+                where
+                    __E: ::serde::de::Error,
+                {
+                    #from_signed
+                }
+            },
+            quote! {
+                if deserializer.is_human_readable() {
+                    deserializer.deserialize_any(__Visitor)
+                } else {
+                    deserializer.deserialize_str(__Visitor)
+                }
+            },
+        )
+    } else {
+        (
+            format!("a variant name of {}", name),
+            quote! {},
+            quote! { deserializer.deserialize_str(__Visitor) },
+        )
+    };
+
+    let serialize = quote! {
+        match self {
+            #other_arms
+            _ => serializer.serialize_str(self.name()),
+        }
+    };
+
+    let deserialize = quote! {
+        const VARIANTS: &[&str] = &[#(#unit_names),*];
+
+        struct __Visitor;
+
+        impl<'de> ::serde::de::Visitor<'de> for __Visitor {
+            type Value = #name;
+
+            fn expecting(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                formatter.write_str(#expecting)
+            }
+
+            fn visit_str<__E>(self, value: &str) -> ::core::result::Result<Self::Value, __E>
+            where
+                __E: ::serde::de::Error,
+            {
+                #name::from_name(value).ok_or_else(|| __E::unknown_variant(value, VARIANTS))
+            }
+
+            #visit_numbers
+        }
+
+        #deserialize_call
+    };
+
+    (serialize, deserialize)
+}
+
+/// The smallest unsigned type holding the ordinals of `num_variants` variants.
+fn ordinal_type(num_variants: usize) -> Type {
+    if num_variants <= 1 << 8 {
+        syn::parse_quote!(u8)
+    } else if num_variants <= 1 << 16 {
+        syn::parse_quote!(u16)
+    } else {
+        syn::parse_quote!(u32)
+    }
+}
+
+/// The bodies of `serialize()` and `deserialize()` representing variants by ordinal.
+fn generate_serde_ordinal_impl(
+    name: &syn::Ident,
+    num_variants: usize,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let ordinal_type = ordinal_type(num_variants);
+    let expected = match num_variants {
+        0 => String::new(),
+        1 => ", expected 0".to_owned(),
+        _ => format!(", expected 0 to {}", num_variants - 1),
+    };
+    let unknown = format!("unknown ordinal `{{}}` for {}{}", name, expected);
+
+    let serialize = quote! {
+        ::serde::Serialize::serialize(&(self.ordinal() as #ordinal_type), serializer)
+    };
+    let deserialize = quote! {
+        let value = <#ordinal_type as ::serde::Deserialize>::deserialize(deserializer)?;
+        #name::from_ordinal(value as usize).ok_or_else(|| {
+            ::serde::de::Error::custom(::core::format_args!(#unknown, value))
+        })
+    };
+
+    (serialize, deserialize)
+}
+
+/// The bodies of `serialize()` and `deserialize()` representing variants by discriminant.
+fn generate_serde_discriminant_impl(
+    name: &syn::Ident,
+    other_variant: &Option<(&Variant, Type)>,
+    infallible: bool,
+    num_variants: usize,
+    discriminant_type: &Type,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let encoded_type = encoded_discriminant_type(other_variant, discriminant_type);
+    let discriminant = encoded_discriminant(name, other_variant, discriminant_type, quote! { self });
+    let from_encoded = encoded_discriminant_constructor(name, other_variant, discriminant_type);
+    let from_discriminant = if infallible {
+        quote! { Ok(#from_encoded(value)) }
+    } else {
+        let (expected, expected_argument) = expected_discriminants(name, num_variants);
+        let unknown = format!("unknown discriminant `{{}}` for {}{}", name, expected);
+        quote! {
+            #name::from_discriminant(value).ok_or_else(|| {
+                ::serde::de::Error::custom(::core::format_args!(#unknown, value #expected_argument))
+            })
+        }
+    };
+
+    let serialize = quote! {
+        ::serde::Serialize::serialize(&#discriminant, serializer)
+    };
+    let deserialize = quote! {
+        let value = <#encoded_type as ::serde::Deserialize>::deserialize(deserializer)?;
+        #from_discriminant
+    };
+
+    (serialize, deserialize)
+}
+
+fn generate_schemars_impl(
+    name: &syn::Ident,
+    unit_names: &[LitStr],
+    other_variant: &Option<(&Variant, Type)>,
+    serde_mode: SerdeMode,
+) -> proc_macro2::TokenStream {
+    let name_str = name.to_string();
+
+    // Mirrors the serde representation in JSON, including the raw values of the "other" variant
+    let schema = match (serde_mode, other_variant.is_some()) {
+        (SerdeMode::Name | SerdeMode::Auto, false) => quote! {
+            ::schemars::json_schema!({
+                "type": "string",
+                "enum": [#(#unit_names),*],
+            })
+        },
+        (SerdeMode::Name | SerdeMode::Auto, true) => quote! {
+            ::schemars::json_schema!({
+                "oneOf": [
+                    { "type": "string", "enum": [#(#unit_names),*] },
+                    { "type": "integer" },
+                ],
+            })
+        },
+        (SerdeMode::Discriminant, false) => quote! {
+            ::schemars::json_schema!({
+                "type": "integer",
+                "enum": #name::values().map(|value| value.discriminant()).collect::<::std::vec::Vec<_>>(),
+            })
+        },
+        (SerdeMode::Discriminant, true) => quote! {
+            ::schemars::json_schema!({
+                "type": "integer",
+            })
+        },
+        (SerdeMode::Ordinal, _) => quote! {
+            ::schemars::json_schema!({
+                "type": "integer",
+                "enum": (0..#name::len()).collect::<::std::vec::Vec<_>>(),
+            })
+        },
+    };
+
+    quote! {
+        impl ::schemars::JsonSchema for #name {
+            fn schema_name() -> ::std::borrow::Cow<'static, str> {
+                #name_str.into()
+            }
+
+            fn schema_id() -> ::std::borrow::Cow<'static, str> {
+                ::core::concat!(::core::module_path!(), "::", #name_str).into()
+            }
+
+            fn json_schema(_: &mut ::schemars::SchemaGenerator) -> ::schemars::Schema {
+                #schema
+            }
+        }
+    }
+}
+
+fn generate_utoipa_impl(
+    ast: &DeriveInput,
+    unit_variants: &[&Variant],
+    unit_names: &[LitStr],
+    other_variant: &Option<(&Variant, Type)>,
+    serde_mode: SerdeMode,
+) -> proc_macro2::TokenStream {
+    let name = &ast.ident;
+    let name_str = name.to_string();
+    let schema = quote! { ::utoipa::openapi::schema };
+
+    let description = |doc: Option<String>| match doc {
+        Some(doc) => quote! { Some(#doc) },
+        None => quote! { None::<&str> },
+    };
+    let enum_description = description(doc_comment(&ast.attrs));
+
+    // Mirrors the serde representation in JSON, including the raw values of the "other" variant
+    let (value_type, values): (_, Vec<_>) = match serde_mode {
+        SerdeMode::Name | SerdeMode::Auto => (
+            quote! { #schema::Type::String },
+            unit_names.iter().map(|variant_str| quote! { #variant_str }).collect(),
+        ),
+        SerdeMode::Discriminant => (
+            quote! { #schema::Type::Integer },
+            unit_variants.iter().map(|variant| {
+                let variant_name = &variant.ident;
+                quote! { #name::#variant_name.discriminant() }
+            }).collect(),
+        ),
+        SerdeMode::Ordinal => (
+            quote! { #schema::Type::Integer },
+            (0..unit_variants.len()).map(|index| quote! { #index }).collect(),
+        ),
+    };
+    let other_schema = other_variant.as_ref().map(|_| quote! {
+        #schema::ObjectBuilder::new().schema_type(#schema::Type::Integer)
+    });
+    let variant_docs: Vec<_> = unit_variants.iter().map(|variant| doc_comment(&variant.attrs)).collect();
+
+    let body = if serde_mode == SerdeMode::Discriminant && other_schema.is_some() {
+        quote! {
+            #other_schema.description(#enum_description).into()
+        }
+    } else if other_schema.is_none() && variant_docs.iter().all(Option::is_none) {
+        // An empty array leaves nothing to infer the element type from
+        let values = if values.is_empty() { quote! { [] as [&str; 0] } } else { quote! { [#(#values),*] } };
+        quote! {
+            #schema::ObjectBuilder::new()
+                .schema_type(#value_type)
+                .enum_values(Some(#values))
+                .description(#enum_description)
+                .into()
+        }
+    } else {
+        // Variant descriptions need one sub-schema per variant
+        let items = values.iter().zip(variant_docs).map(|(value, doc)| {
+            let variant_description = description(doc);
+            quote! {
+                .item(#schema::ObjectBuilder::new()
+                    .schema_type(#value_type)
+                    .enum_values(Some([#value]))
+                    .description(#variant_description))
+            }
+        });
+        let other_item = other_schema.map(|other_schema| quote! { .item(#other_schema) });
+
+        quote! {
+            #schema::OneOfBuilder::new()
+                #(#items)*
+                #other_item
+                .description(#enum_description)
+                .into()
+        }
+    };
+
+    quote! {
+        impl ::utoipa::PartialSchema for #name {
+            fn schema() -> ::utoipa::openapi::RefOr<#schema::Schema> {
+                #body
+            }
+        }
+
+        impl ::utoipa::ToSchema for #name {
+            fn name() -> ::std::borrow::Cow<'static, str> {
+                #name_str.into()
+            }
+        }
+    }
+}
+
+fn generate_ts_rs_impl(
+    ast: &DeriveInput,
+    unit_names: &[LitStr],
+    other_variant: &Option<(&Variant, Type)>,
+    serde_mode: SerdeMode,
+) -> proc_macro2::TokenStream {
+    let name = &ast.ident;
+    let name_str = name.to_string();
+    let output_path = format!("{}.ts", name_str);
+
+    // Rust and TypeScript string literals share their escapes for the characters a name can hold
+    let union = |members: Vec<String>| match members.is_empty() {
+        true => "never".to_owned(),
+        false => members.join(" | "),
+    };
+
+    // Mirrors the serde representation in JSON, including the raw values of the "other" variant
+    let inline = match (serde_mode, other_variant) {
+        (SerdeMode::Name | SerdeMode::Auto, None) => {
+            let names = union(unit_names.iter().map(|variant_str| format!("{:?}", variant_str.value())).collect());
+            quote! { #names.to_owned() }
+        }
+        (SerdeMode::Name | SerdeMode::Auto, Some((_, other_type))) => {
+            let names = unit_names.iter().map(|variant_str| format!("{:?} | ", variant_str.value())).collect::<String>();
+            quote! { ::std::format!("{}{}", #names, <#other_type as ::ts_rs::TS>::name(cfg)) }
+        }
+        (SerdeMode::Discriminant, None) => quote! {
+            match #name::values().map(|value| value.discriminant().to_string()).collect::<::std::vec::Vec<_>>() {
+                discriminants if discriminants.is_empty() => "never".to_owned(),
+                discriminants => discriminants.join(" | "),
+            }
+        },
+        (SerdeMode::Discriminant, Some((_, other_type))) => quote! {
+            <#other_type as ::ts_rs::TS>::name(cfg)
+        },
+        (SerdeMode::Ordinal, _) => {
+            let ordinals = union((0..unit_names.len()).map(|ordinal| ordinal.to_string()).collect());
+            quote! { #ordinals.to_owned() }
+        }
+    };
+    let cfg = match (serde_mode, other_variant) {
+        (SerdeMode::Name | SerdeMode::Auto | SerdeMode::Discriminant, Some(_)) => quote! { cfg },
+        _ => quote! { _ },
+    };
+
+    let docs = match doc_comment(&ast.attrs) {
+        Some(doc) => {
+            let jsdoc = format!("/**\n{}\n */\n", doc.lines().map(|line| format!(" * {}", line).trim_end().to_owned())
+                .collect::<Vec<_>>().join("\n"));
+            quote! { Some(#jsdoc.to_owned()) }
+        }
+        None => quote! { None },
+    };
+
+    quote! {
+        impl ::ts_rs::TS for #name {
+            type WithoutGenerics = Self;
+            type OptionInnerType = Self;
+
+            fn docs() -> ::std::option::Option<::std::string::String> {
+                #docs
+            }
+
+            fn ident(_: &::ts_rs::Config) -> ::std::string::String {
+                #name_str.to_owned()
+            }
+
+            fn name(_: &::ts_rs::Config) -> ::std::string::String {
+                #name_str.to_owned()
+            }
+
+            fn inline(#cfg: &::ts_rs::Config) -> ::std::string::String {
+                #inline
+            }
+
+            fn decl(cfg: &::ts_rs::Config) -> ::std::string::String {
+                ::std::format!("type {} = {};", #name_str, Self::inline(cfg))
+            }
+
+            fn decl_concrete(cfg: &::ts_rs::Config) -> ::std::string::String {
+                Self::decl(cfg)
+            }
+
+            fn output_path() -> ::std::option::Option<::std::path::PathBuf> {
+                Some(::std::path::PathBuf::from(#output_path))
+            }
+        }
+    }
+}
+
+fn generate_specta_impl(
+    ast: &DeriveInput,
+    unit_variants: &[&Variant],
+    unit_names: &[LitStr],
+    other_variant: &Option<(&Variant, Type)>,
+    discriminant_type: &Type,
+    serde_mode: SerdeMode,
+) -> proc_macro2::TokenStream {
+    let name = &ast.ident;
+    let name_str = name.to_string();
+    let construct = quote! { ::specta::internal::construct };
+    let datatype = quote! { ::specta::datatype };
+    let enum_docs = doc_comment(&ast.attrs).unwrap_or_default();
+
+    // specta only has literals for integers up to 32 bits, wider discriminants are described by their type
+    let literal_type = ["i8", "i16", "i32", "u8", "u16", "u32"].into_iter()
+        .find(|literal| discriminant_type.to_token_stream().to_string() == *literal)
+        .map(|literal| format_ident!("{}", literal));
+
+    // Mirrors the serde representation in JSON as an untagged union, including the raw values of the "other"
+    // variant. Variant docs are left out, TypeScript exporters would print them inside the union.
+    let variant_type = |variant: &Variant, index: usize| {
+        let variant_name = &variant.ident;
+        match serde_mode {
+            SerdeMode::Name | SerdeMode::Auto => {
+                let variant_str = &unit_names[index];
+                quote! { #datatype::LiteralType::String(#variant_str.to_owned()).into() }
+            }
+            SerdeMode::Discriminant => match &literal_type {
+                Some(literal) => quote! { #datatype::LiteralType::#literal(#name::#variant_name.discriminant()).into() },
+                None => quote! { <#discriminant_type as ::specta::Type>::inline(type_map, ::specta::Generics::Definition) },
+            },
+            SerdeMode::Ordinal => {
+                let ordinal = index as u32;
+                quote! { #datatype::LiteralType::u32(#ordinal).into() }
+            }
+        }
+    };
+    let variants = unit_variants.iter().enumerate().map(|(index, variant)| {
+        let variant_str = variant.ident.to_string();
+        let ty = variant_type(variant, index);
+        quote! {
+            (#variant_str.into(), #construct::enum_variant(false, None, "".into(), #construct::enum_variant_unnamed(
+                ::std::vec![#construct::field(false, false, None, "".into(), Some(#ty))],
+            )))
+        }
+    });
+    let other_variant = other_variant.as_ref().map(|(variant, other_type)| {
+        let variant_str = variant.ident.to_string();
+        quote! {
+            (#variant_str.into(), #construct::enum_variant(false, None, "".into(), #construct::enum_variant_unnamed(
+                ::std::vec![#construct::field(false, false, None, "".into(), Some(
+                    <#other_type as ::specta::Type>::inline(type_map, ::specta::Generics::Definition),
+                ))],
+            )))
+        }
+    });
+
+    quote! {
+        impl ::specta::Type for #name {
+            fn inline(type_map: &mut ::specta::TypeCollection, _: ::specta::Generics) -> #datatype::DataType {
+                #construct::r#enum(
+                    #name_str.into(),
+                    <Self as ::specta::NamedType>::sid(),
+                    #datatype::EnumRepr::Untagged,
+                    false,
+                    ::std::vec![],
+                    ::std::vec![#(#variants,)* #other_variant],
+                ).into()
+            }
+
+            fn reference(type_map: &mut ::specta::TypeCollection, _: &[#datatype::DataType]) -> #datatype::reference::Reference {
+                #datatype::reference::reference::<Self>(
+                    type_map,
+                    #construct::data_type_reference(#name_str.into(), <Self as ::specta::NamedType>::sid(), ::std::vec![]),
+                )
+            }
+        }
+
+        impl ::specta::NamedType for #name {
+            fn sid() -> ::specta::SpectaID {
+                #construct::sid(#name_str, ::core::concat!("::", ::core::module_path!(), ":", ::core::line!(), ":", ::core::column!()))
+            }
+
+            fn named_data_type(type_map: &mut ::specta::TypeCollection, generics: &[#datatype::DataType]) -> #datatype::NamedDataType {
+                #construct::named_data_type(
+                    #name_str.into(),
+                    #enum_docs.into(),
+                    None,
+                    Self::sid(),
+                    #construct::impl_location(::core::concat!(::core::file!(), ":", ::core::line!(), ":", ::core::column!())),
+                    <Self as ::specta::Type>::inline(type_map, ::specta::Generics::Provided(generics)),
+                )
+            }
+
+            fn definition_named_data_type(type_map: &mut ::specta::TypeCollection) -> #datatype::NamedDataType {
+                Self::named_data_type(type_map, &[])
+            }
+        }
+    }
+}
+
+fn generate_clap_impl(
+    name: &syn::Ident,
+    unit_variants: &[&Variant],
+    unit_names: &[LitStr],
+    other_variant: &Option<(&Variant, Type)>,
+) -> proc_macro2::TokenStream {
+    let scrutinee = self_scrutinee(other_variant);
+    let variant_exprs = unit_variants.iter().map(|variant| {
+        let variant_name = &variant.ident;
+        quote! { #name::#variant_name }
+    });
+
+    let possible_value_arms = unit_variants.iter().zip(unit_names).map(|(variant, variant_str)| {
+        let variant_name = &variant.ident;
+        // Like clap's own derive, only the first paragraph of the doc comment is used as help
+        let help = doc_comment(&variant.attrs).map(|doc| {
+            let summary = doc.split("\n\n").next().unwrap_or_default().replace('\n', " ");
+            quote! { .help(#summary) }
+        });
+        quote! { #name::#variant_name => Some(::clap::builder::PossibleValue::new(#variant_str)#help) }
+    });
+
+    let other_arm = other_variant.as_ref().map(|(variant, _)| {
+        let variant_name = &variant.ident;
+        quote! { #name::#variant_name(_) => None, }
+    });
+
+    quote! {
+        impl ::clap::ValueEnum for #name {
+            fn value_variants<'a>() -> &'a [Self] {
+                &[#(#variant_exprs),*]
+            }
+
+            fn to_possible_value(&self) -> ::core::option::Option<::clap::builder::PossibleValue> {
+                match #scrutinee {
+                    #(#possible_value_arms,)*
+                    #other_arm
+                }
+            }
+        }
+    }
+}
+
+fn generate_egui_impl(has_display: bool) -> proc_macro2::TokenStream {
+    let text = if has_display { quote! { display_str } } else { quote! { name } };
+
+    quote! {
+        /// Shows an `egui::ComboBox` labelled `label` for picking a variant, writing the pick to `value`.
+        ///
+        /// The returned response is marked as changed when a different variant was picked.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum)]
+        /// enum Quality {
+        ///     Low,
+        ///     High,
+        /// }
+        ///
+        /// if Quality::ui_combo_box(ui, "Quality", &mut settings.quality).changed() {
+        ///     settings.save();
+        /// }
+        /// ```
+        pub fn ui_combo_box(
+            ui: &mut ::egui::Ui,
+            label: impl ::core::convert::Into<::egui::WidgetText>,
+            value: &mut Self,
+        ) -> ::egui::Response {
+            let mut __changed = false;
+            let mut __response = ::egui::ComboBox::from_label(label)
+                .selected_text(value.#text())
+                .show_ui(ui, |ui| {
+                    for __variant in Self::values() {
+                        let __selected = __variant.ordinal() == value.ordinal();
+                        if ui.selectable_label(__selected, __variant.#text()).clicked() && !__selected {
+                            *value = __variant;
+                            __changed = true;
+                        }
+                    }
+                })
+                .response;
+            if __changed {
+                __response.mark_changed();
+            }
+            __response
+        }
+    }
+}
+
+fn generate_par_values_impl(num_variants: usize) -> proc_macro2::TokenStream {
+    quote! {
+        /// Returns an indexed parallel iterator over all unit variants of the enum, in declaration order.
+        ///
+        /// Note: This does not include values from the "other" variant, if present.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// use rayon::prelude::*;
+        ///
+        /// #[derive(UnitEnum)]
+        /// enum Example {
+        ///     A,
+        ///     B,
+        /// }
+        ///
+        /// let names: Vec<_> = Example::par_values().map(|value| value.name()).collect();
+        /// assert_eq!(names, vec!["A", "B"]);
+        /// ```
+        pub fn par_values() -> impl ::rayon::iter::IndexedParallelIterator<Item = Self> {
+            ::rayon::iter::ParallelIterator::map(
+                ::rayon::iter::IntoParallelIterator::into_par_iter(0..#num_variants),
+                |ordinal| match Self::from_ordinal(ordinal) {
+                    Some(value) => value,
+                    None => unreachable!(),
+                },
+            )
+        }
+    }
+}
+
+fn generate_arrow_impl(name: &syn::Ident, unit_names: &[LitStr], num_variants: usize) -> proc_macro2::TokenStream {
+    let not_utf8 = format!("expected a Utf8 dictionary for {}, found {{}}", name);
+    let unknown = format!("unknown {} variant `{{}}`{}", name, expected_names(unit_names));
+
+    quote! {
+        /// Builds a dictionary-encoded Arrow array of the values, whose dictionary holds the variant names and
+        /// whose keys are ordinals.
+        ///
+        /// Note: The "other" variant has no entry in the dictionary and is written as null.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// use arrow::datatypes::UInt8Type;
+        ///
+        /// #[derive(UnitEnum)]
+        /// enum Example {
+        ///     A,
+        ///     B,
+        /// }
+        ///
+        /// let array = Example::to_arrow_dictionary::<UInt8Type>(&[Example::B, Example::B, Example::A])?;
+        /// assert_eq!(array.keys().values(), &[1, 1, 0]);
+        /// ```
+        pub fn to_arrow_dictionary<__K: ::arrow::datatypes::ArrowDictionaryKeyType>(
+            values: &[Self],
+        ) -> ::core::result::Result<::arrow::array::DictionaryArray<__K>, ::arrow::error::ArrowError> {
+            let keys = values.iter().map(|value| match value.ordinal() {
+                ordinal if ordinal < #num_variants => {
+                    <__K::Native as ::arrow::datatypes::ArrowNativeType>::from_usize(ordinal)
+                        .map(Some)
+                        .ok_or_else(|| ::arrow::error::ArrowError::DictionaryKeyOverflowError)
+                }
+                _ => Ok(None),
+            }).collect::<::core::result::Result<::arrow::array::PrimitiveArray<__K>, _>>()?;
+            let dictionary = ::arrow::array::StringArray::from(::std::vec![#(#unit_names),*] as ::std::vec::Vec<&str>);
+            ::arrow::array::DictionaryArray::try_new(keys, ::std::sync::Arc::new(dictionary))
+        }
+
+        /// Decodes a dictionary-encoded Arrow array of variant names, looking up each dictionary entry once.
+        ///
+        /// Returns `None` for null entries, and an error if the dictionary isn't `Utf8` or holds an unknown name.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// use arrow::datatypes::UInt8Type;
+        ///
+        /// #[derive(UnitEnum, PartialEq, Debug)]
+        /// enum Example {
+        ///     A,
+        ///     B,
+        /// }
+        ///
+        /// let array = Example::to_arrow_dictionary::<UInt8Type>(&[Example::B, Example::A])?;
+        /// assert_eq!(Example::from_arrow_dictionary(&array)?, vec![Some(Example::B), Some(Example::A)]);
+        /// ```
+        pub fn from_arrow_dictionary<__K: ::arrow::datatypes::ArrowDictionaryKeyType>(
+            array: &::arrow::array::DictionaryArray<__K>,
+        ) -> ::core::result::Result<::std::vec::Vec<::core::option::Option<Self>>, ::arrow::error::ArrowError> {
+            let values = array.values();
+            let dictionary = ::arrow::array::Array::as_any(values.as_ref())
+                .downcast_ref::<::arrow::array::StringArray>()
+                .ok_or_else(|| ::arrow::error::ArrowError::InvalidArgumentError(
+                    ::std::format!(#not_utf8, ::arrow::array::Array::data_type(values.as_ref()))
+                ))?;
+            let ordinals = dictionary.iter().map(|name| match name {
+                Some(name) => match Self::from_name(name) {
+                    Some(value) => Ok(Some(value.ordinal())),
+                    None => Err(::arrow::error::ArrowError::InvalidArgumentError(
+                        ::std::format!(#unknown, name)
+                    )),
+                },
+                None => Ok(None),
+            }).collect::<::core::result::Result<::std::vec::Vec<_>, _>>()?;
+            Ok(array.keys().iter().map(|key| {
+                let key = ::arrow::datatypes::ArrowNativeType::as_usize(key?);
+                ordinals.get(key).copied().flatten().and_then(Self::from_ordinal)
+            }).collect())
+        }
+    }
+}
+
+fn generate_rand_impl(
+    name: &syn::Ident,
+    num_variants: usize,
+    weight: Option<&AssocField>,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let random_weighted_impl = weight.map(|field| {
+        let AssocField { name: field_name, ty, .. } = field;
+        let doc = format!("Returns a unit variant chosen at random, with a probability proportional to its `{}` constant.",
+                          field_name);
+        let weights_error = format!("the weights of {} must be integers that add up to more than zero", name);
+        quote! {
+            #[doc = #doc]
+            ///
+            /// The "other" variant is never returned. The cumulative weights are computed at compile time, which
+            /// fails if a weight is negative or fractional, or if they add up to zero.
+            ///
+            /// # Examples
+            ///
+            /// ```ignore
+            /// # use unit_enum::UnitEnum;
+            /// #[derive(UnitEnum)]
+            /// #[unit_enum(assoc(weight: u32 = 1), random_weight = weight)]
+            /// enum Loot {
+            ///     #[unit_enum(assoc(weight = 90))]
+            ///     Common,
+            ///     #[unit_enum(assoc(weight = 10))]
+            ///     Rare,
+            /// }
+            ///
+            /// let loot = Loot::random_weighted(&mut rand::rng());
+            /// ```
+            pub fn random_weighted<__R: ::rand::Rng + ?Sized>(rng: &mut __R) -> Self {
+                const __CUMULATIVE: [u64; #num_variants] = {
+                    let mut cumulative = [0; #num_variants];
+                    let mut total: u64 = 0;
+                    let mut ordinal = 0;
+                    while ordinal < #num_variants {
+                        let weight: #ty = #name::from_ordinal(ordinal).unwrap().#field_name();
+                        // Negative integers survive the round trip through u64, but not the one through i128
+                        assert!(weight as i128 >= 0 && weight as u64 as #ty == weight, #weights_error);
+                        total += weight as u64;
+                        cumulative[ordinal] = total;
+                        ordinal += 1;
+                    }
+                    assert!(total > 0, #weights_error);
+                    cumulative
+                };
+                let points = ::rand::distr::Uniform::new(0, __CUMULATIVE[#num_variants - 1]).unwrap();
+                let point = ::rand::distr::Distribution::sample(&points, rng);
+                Self::from_ordinal(__CUMULATIVE.partition_point(|&cumulative| cumulative <= point)).unwrap()
+            }
+        }
+    });
+
+    let random_impl = quote! {
+        /// Returns a unit variant chosen uniformly at random.
+        ///
+        /// The "other" variant is never returned.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum)]
+        /// enum Example {
+        ///     A,
+        ///     B,
+        /// }
+        ///
+        /// let value = Example::random(&mut rand::rng());
+        /// assert!(value.ordinal() < Example::len());
+        /// ```
+        pub fn random<__R: ::rand::Rng + ?Sized>(rng: &mut __R) -> Self {
+            let ordinals = ::rand::distr::Uniform::new(0, #num_variants).unwrap();
+            Self::from_ordinal(::rand::distr::Distribution::sample(&ordinals, rng)).unwrap()
+        }
+
+        #random_weighted_impl
+    };
+
+    let rand_impl = quote! {
+        impl ::rand::distr::Distribution<#name> for ::rand::distr::StandardUniform {
+            fn sample<__R: ::rand::Rng + ?Sized>(&self, rng: &mut __R) -> #name {
+                #name::random(rng)
+            }
+        }
+    };
+
+    (random_impl, rand_impl)
+}
+
+fn generate_arbitrary_impl(
+    name: &syn::Ident,
+    num_variants: usize,
+    arbitrary_other: bool,
+) -> proc_macro2::TokenStream {
+    let body = if arbitrary_other {
+        // One extra choice stands for a raw discriminant, which may still land on a unit variant
+        quote! {
+            match u.choose_index(#num_variants + 1)? {
+                #num_variants => Ok(#name::from_discriminant(u.arbitrary()?)),
+                ordinal => Ok(#name::from_ordinal(ordinal).unwrap()),
+            }
+        }
+    } else {
+        quote! {
+            Ok(#name::from_ordinal(u.choose_index(#num_variants)?).unwrap())
+        }
+    };
+
+    quote! {
+        impl<'a> ::arbitrary::Arbitrary<'a> for #name {
+            fn arbitrary(u: &mut ::arbitrary::Unstructured<'a>) -> ::arbitrary::Result<Self> {
+                #body
+            }
+        }
+    }
+}
+
+fn generate_proptest_impl(
+    name: &syn::Ident,
+    num_variants: usize,
+    arbitrary_other: bool,
+    discriminant_type: &Type,
+) -> proc_macro2::TokenStream {
+    // Drawing ordinals lets failing cases shrink towards the first variant
+    let unit_strategy = quote! {
+        ::proptest::strategy::Strategy::prop_map(0..#num_variants, |ordinal| {
+            #name::from_ordinal(ordinal).unwrap()
+        })
+    };
+    let other_strategy = quote! {
+        ::proptest::strategy::Strategy::prop_map(
+            ::proptest::arbitrary::any::<#discriminant_type>(),
+            #name::from_discriminant,
+        )
+    };
+
+    let strategy = match (num_variants > 0, arbitrary_other) {
+        (true, true) => quote! {
+            ::proptest::strategy::Strategy::prop_union(
+                ::proptest::strategy::Strategy::boxed(#unit_strategy),
+                ::proptest::strategy::Strategy::boxed(#other_strategy),
+            )
+        },
+        (true, false) => unit_strategy,
+        (false, _) => other_strategy,
+    };
+
+    quote! {
+        impl ::proptest::arbitrary::Arbitrary for #name {
+            type Parameters = ();
+            type Strategy = ::proptest::strategy::BoxedStrategy<Self>;
+
+            fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+                ::proptest::strategy::Strategy::boxed(#strategy)
+            }
+        }
+    }
+}
+
+fn generate_quickcheck_impl(
+    name: &syn::Ident,
+    num_variants: usize,
+    arbitrary_other: bool,
+    discriminant_type: &Type,
+) -> proc_macro2::TokenStream {
+    let choices = (0..num_variants + usize::from(arbitrary_other)).collect::<Vec<_>>();
+    let other_arm = arbitrary_other.then(|| quote! {
+        #num_variants => #name::from_discriminant(<#discriminant_type as ::quickcheck::Arbitrary>::arbitrary(g)),
+    });
+
+    quote! {
+        impl ::quickcheck::Arbitrary for #name {
+            fn arbitrary(g: &mut ::quickcheck::Gen) -> Self {
+                match *g.choose(&[#(#choices),*]).unwrap() {
+                    #other_arm
+                    ordinal => #name::from_ordinal(ordinal).unwrap(),
+                }
+            }
+
+            fn shrink(&self) -> ::std::boxed::Box<dyn ::std::iter::Iterator<Item = Self>> {
+                // Shrinks towards the earlier unit variants, the first one being the simplest
+                ::std::boxed::Box::new((0..self.ordinal()).filter_map(#name::from_ordinal))
+            }
+        }
+    }
+}
+
+fn generate_defmt_impl(
+    name: &syn::Ident,
+    unit_variants: &[&Variant],
+    unit_names: &[LitStr],
+    other_variant: &Option<(&Variant, Type)>,
+    other_name: &Option<LitStr>,
+    discriminant_type: &Type,
+) -> proc_macro2::TokenStream {
+    let scrutinee = self_scrutinee(other_variant);
+    // Names go into the format string so defmt interns them; the typed hint keeps the value compact
+    let format_string = |variant_str: &LitStr, ty: &Type| {
+        let escaped = variant_str.value().replace('{', "{{").replace('}', "}}");
+        let hint = ty.to_token_stream().to_string();
+        LitStr::new(&format!("{}({{={}}})", escaped, hint), variant_str.span())
+    };
+
+    let unit_arms = unit_variants.iter().zip(unit_names).map(|(variant, variant_str)| {
+        let variant_name = &variant.ident;
+        let format = format_string(variant_str, discriminant_type);
+        quote! { #name::#variant_name => ::defmt::write!(f, #format, self.discriminant()) }
+    });
+
+    let other_arm = other_variant.as_ref().zip(other_name.as_ref()).map(|((variant, other_type), variant_str)| {
+        let variant_name = &variant.ident;
+        let format = format_string(variant_str, other_type);
+        quote! { #name::#variant_name(val) => ::defmt::write!(f, #format, *val), }
+    });
+
+    quote! {
+        impl ::defmt::Format for #name {
+            fn format(&self, f: ::defmt::Formatter) {
+                match #scrutinee {
+                    #(#unit_arms,)*
+                    #other_arm
+                }
+            }
+        }
+    }
+}
+
+fn generate_bevy_impl(ast: &DeriveInput) -> proc_macro2::TokenStream {
+    let name = &ast.ident;
+    let name_str = name.to_string();
+    let docs = ast.attrs.iter().filter(|attr| attr.path().is_ident("doc"));
+
+    // impl_reflect! only reads the shape of the enum, so variants are restated without their other attributes
+    let variants = match &ast.data {
+        Data::Enum(data_enum) => data_enum.variants.iter().map(|variant| {
+            let variant_docs = variant.attrs.iter().filter(|attr| attr.path().is_ident("doc"));
+            let variant_name = &variant.ident;
+            let fields = variant.fields.iter().map(|field| &field.ty);
+            match variant.fields {
+                Fields::Unit => quote! { #(#variant_docs)* #variant_name },
+                _ => quote! { #(#variant_docs)* #variant_name(#(#fields),*) },
+            }
+        }).collect(),
+        _ => Vec::new(),
+    };
+
+    // The type path comes from module_path!(), which impl_reflect! cannot take in place of a literal
+    quote! {
+        ::bevy_reflect::impl_reflect! {
+            #[reflect(type_path = false)]
+            #(#docs)*
+            enum #name {
+                #(#variants),*
+            }
+        }
+
+        impl ::bevy_reflect::TypePath for #name {
+            fn type_path() -> &'static str {
+                ::core::concat!(::core::module_path!(), "::", #name_str)
+            }
+
+            fn short_type_path() -> &'static str {
+                #name_str
+            }
+
+            fn type_ident() -> ::core::option::Option<&'static str> {
+                Some(#name_str)
+            }
+
+            fn module_path() -> ::core::option::Option<&'static str> {
+                Some(::core::module_path!())
+            }
+        }
+    }
+}
+
+fn generate_valuable_impl(name: &syn::Ident, other_variant: &Option<(&Variant, Type)>) -> proc_macro2::TokenStream {
+    let name_str = name.to_string();
+    // The "other" payload is recorded as is, since it may not fit in the repr
+    let discriminant = match other_variant {
+        Some((variant, _)) => {
+            let variant_name = &variant.ident;
+            quote! {
+                match self {
+                    #name::#variant_name(val) => __visit(::valuable::Valuable::as_value(val)),
+                    _ => __visit(::valuable::Valuable::as_value(&self.discriminant())),
+                }
+            }
+        }
+        None => quote! { __visit(::valuable::Valuable::as_value(&self.discriminant())) },
+    };
+
+    quote! {
+        impl ::valuable::Valuable for #name {
+            fn as_value(&self) -> ::valuable::Value<'_> {
+                ::valuable::Value::Structable(self)
+            }
+
+            fn visit(&self, visitor: &mut dyn ::valuable::Visit) {
+                const __FIELDS: &[::valuable::NamedField<'static>] =
+                    &[::valuable::NamedField::new("name"), ::valuable::NamedField::new("discriminant")];
+                let mut __visit = |discriminant: ::valuable::Value<'_>| {
+                    let __values = [::valuable::Value::String(self.name()), discriminant];
+                    visitor.visit_named_fields(&::valuable::NamedValues::new(__FIELDS, &__values));
+                };
+                #discriminant
+            }
+        }
+
+        impl ::valuable::Structable for #name {
+            fn definition(&self) -> ::valuable::StructDef<'_> {
+                const __FIELDS: &[::valuable::NamedField<'static>] =
+                    &[::valuable::NamedField::new("name"), ::valuable::NamedField::new("discriminant")];
+                ::valuable::StructDef::new_static(#name_str, ::valuable::Fields::Named(__FIELDS))
+            }
+        }
+    }
+}
+
+fn generate_metrics_impl(name: &syn::Ident) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let metrics_label_impl = quote! {
+        /// Returns a `metrics::Label` with the given key and the variant name as its value, without allocating.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum)]
+        /// enum Example {
+        ///     A,
+        ///     B,
+        /// }
+        ///
+        /// metrics::counter!("requests", vec![Example::B.metrics_label("example")]).increment(1);
+        /// ```
+        pub const fn metrics_label(&self, key: &'static str) -> ::metrics::Label {
+            ::metrics::Label::from_static_parts(key, self.name())
+        }
+    };
+
+    // Lets label values be given as `"key" => variant` in the metrics macros
+    let metrics_impl = quote! {
+        impl From<#name> for ::metrics::SharedString {
+            fn from(value: #name) -> Self {
+                ::metrics::SharedString::const_str(value.name())
+            }
+        }
+    };
+
+    (metrics_label_impl, metrics_impl)
+}
+
+fn generate_ufmt_impl(
+    name: &syn::Ident,
+    other_variant: &Option<(&Variant, Type)>,
+) -> proc_macro2::TokenStream {
+    // Debug output mirrors `#[derive(Debug)]`, which shows the "other" payload
+    let debug_body = match other_variant {
+        Some((variant, _)) => {
+            let variant_name = &variant.ident;
+            quote! {
+                match self {
+                    #name::#variant_name(val) => {
+                        f.write_str(self.name())?;
+                        f.write_str("(")?;
+                        ::ufmt::uDebug::fmt(val, f)?;
+                        f.write_str(")")
+                    }
+                    _ => f.write_str(self.name()),
+                }
+            }
+        }
+        None => quote! { f.write_str(self.name()) },
+    };
+
+    quote! {
+        impl ::ufmt::uDisplay for #name {
+            fn fmt<__W>(&self, f: &mut ::ufmt::Formatter<'_, __W>) -> ::core::result::Result<(), __W::Error>
+            where
+                __W: ::ufmt::uWrite + ?Sized,
+            {
+                f.write_str(self.name())
+            }
+        }
+
+        impl ::ufmt::uDebug for #name {
+            fn fmt<__W>(&self, f: &mut ::ufmt::Formatter<'_, __W>) -> ::core::result::Result<(), __W::Error>
+            where
+                __W: ::ufmt::uWrite + ?Sized,
+            {
+                #debug_body
+            }
+        }
+    }
+}
+
+fn generate_sqlx_name_impl(
+    name: &syn::Ident,
+    unit_variants: &[&Variant],
+    unit_names: &[LitStr],
+    other_variant: &Option<(&Variant, Type)>,
+    discriminant_type: &Type,
+) -> proc_macro2::TokenStream {
+    let scrutinee = self_scrutinee(other_variant);
+    let encode_arms = unit_variants.iter().zip(unit_names).map(|(variant, variant_str)| {
+        let variant_name = &variant.ident;
+        quote! { #name::#variant_name => <&'static str as ::sqlx::Encode<'q, __DB>>::encode(#variant_str, buf) }
+    });
+
+    // Like the serde feature, the "other" variant is stored as its raw value in decimal
+    let (other_arm, other_bound, parse_other) = match other_variant {
+        Some((variant, other_type)) => {
+            let variant_name = &variant.ident;
+            let from_other = other_value_constructor(name, other_type, discriminant_type);
+            (
+                Some(quote! {
+                    #name::#variant_name(val) => {
+                        <::std::string::String as ::sqlx::Encode<'q, __DB>>::encode(val.to_string(), buf)
+                    }
+                }),
+                Some(quote! { ::std::string::String: ::sqlx::Encode<'q, __DB>, }),
+                Some(quote! { .or_else(|| value.parse::<#other_type>().ok().map(#from_other)) }),
+            )
+        }
+        None => (None, None, None),
+    };
+    let unknown = format!("unknown variant `{{}}` for {}{}", name, expected_names(unit_names));
+
+    quote! {
+        impl<__DB: ::sqlx::Database> ::sqlx::Type<__DB> for #name
+        where
+            str: ::sqlx::Type<__DB>,
+        {
+            fn type_info() -> <__DB as ::sqlx::Database>::TypeInfo {
+                <str as ::sqlx::Type<__DB>>::type_info()
+            }
+
+            fn compatible(ty: &<__DB as ::sqlx::Database>::TypeInfo) -> bool {
+                <str as ::sqlx::Type<__DB>>::compatible(ty)
+            }
+        }
+
+        impl<'q, __DB: ::sqlx::Database> ::sqlx::Encode<'q, __DB> for #name
+        where
+            &'static str: ::sqlx::Encode<'q, __DB>,
+            #other_bound
+        {
+            fn encode_by_ref(
+                &self,
+                buf: &mut <__DB as ::sqlx::Database>::ArgumentBuffer,
+            ) -> ::core::result::Result<::sqlx::encode::IsNull, ::sqlx::error::BoxDynError> {
+                match #scrutinee {
+                    #(#encode_arms,)*
+                    #other_arm
+                }
+            }
+        }
+
+        impl<'r, __DB: ::sqlx::Database> ::sqlx::Decode<'r, __DB> for #name
+        where
+            &'r str: ::sqlx::Decode<'r, __DB>,
+        {
+            fn decode(
+                value: <__DB as ::sqlx::Database>::ValueRef<'r>,
+            ) -> ::core::result::Result<Self, ::sqlx::error::BoxDynError> {
+                let value = <&'r str as ::sqlx::Decode<'r, __DB>>::decode(value)?;
+                #name::from_name(value)
+                    #parse_other
+                    .ok_or_else(|| ::std::format!(#unknown, value).into())
+            }
+        }
+    }
+}
+
+fn generate_borsh_impl(
+    name: &syn::Ident,
+    other_variant: &Option<(&Variant, Type)>,
+    infallible: bool,
+    num_variants: usize,
+    discriminant_type: &Type,
+) -> proc_macro2::TokenStream {
+    let encoded_type = encoded_discriminant_type(other_variant, discriminant_type);
+    let discriminant = encoded_discriminant(name, other_variant, discriminant_type, quote! { self });
+    let from_encoded = encoded_discriminant_constructor(name, other_variant, discriminant_type);
+    let from_discriminant = if infallible {
+        quote! { Ok(#from_encoded(value)) }
+    } else {
+        let (expected, expected_argument) = expected_discriminants(name, num_variants);
+        let unknown = format!("unknown discriminant `{{}}` for {}{}", name, expected);
+        quote! {
+            #from_encoded(value).ok_or_else(|| {
+                ::borsh::io::Error::new(
+                    ::borsh::io::ErrorKind::InvalidData,
+                    ::std::format!(#unknown, value #expected_argument),
+                )
+            })
+        }
+    };
+
+    // Encoded as the bare integer, so the "other" variant round-trips unknown values
+    quote! {
+        impl ::borsh::BorshSerialize for #name {
+            fn serialize<__W: ::borsh::io::Write>(&self, writer: &mut __W) -> ::borsh::io::Result<()> {
+                ::borsh::BorshSerialize::serialize(&#discriminant, writer)
+            }
+        }
+
+        impl ::borsh::BorshDeserialize for #name {
+            fn deserialize_reader<__R: ::borsh::io::Read>(reader: &mut __R) -> ::borsh::io::Result<Self> {
+                let value = <#encoded_type as ::borsh::BorshDeserialize>::deserialize_reader(reader)?;
+                #from_discriminant
+            }
+        }
+    }
+}
+
+fn generate_rkyv_impl(
+    name: &syn::Ident,
+    vis: &syn::Visibility,
+    other_variant: &Option<(&Variant, Type)>,
+    infallible: bool,
+    discriminant_type: &Type,
+) -> proc_macro2::TokenStream {
+    let archived = format_ident!("Archived{}", name);
+    let archived_doc = format!("An archived [`{}`], stored as its discriminant.", name);
+    let encoded_type = encoded_discriminant_type(other_variant, discriminant_type);
+    let discriminant = encoded_discriminant(name, other_variant, discriminant_type, quote! { self });
+    let from_encoded = encoded_discriminant_constructor(name, other_variant, discriminant_type);
+    let to_native = if infallible {
+        quote! { #from_encoded(#encoded_type::from(self.0)) }
+    } else {
+        quote! {
+            // SAFETY: the discriminant was either written by `resolve()` or validated by `check_bytes()`
+            unsafe { #name::from_discriminant_unchecked(#discriminant_type::from(self.0)) }
+        }
+    };
+    // Every integer is a valid "other" value, so only enums without one need checking
+    let check_bytes = if infallible {
+        quote! { Ok(()) }
+    } else {
+        let invalid = format!("invalid discriminant `{{}}` for {}", name);
+        quote! {
+            #[derive(Debug)]
+            struct __InvalidDiscriminant(#discriminant_type);
+
+            impl ::core::fmt::Display for __InvalidDiscriminant {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    ::core::write!(f, #invalid, self.0)
+                }
+            }
+
+            impl ::std::error::Error for __InvalidDiscriminant {}
+
+            // SAFETY: the caller guarantees that `value` is aligned and points to initialized bytes
+            let discr = #discriminant_type::from(unsafe { (*value).0 });
+            match #name::ordinal_of_discriminant(discr) {
+                Some(_) => Ok(()),
+                None => Err(<__C::Error as ::rkyv::rancor::Source>::new(__InvalidDiscriminant(discr))),
+            }
+        }
+    };
+
+    quote! {
+        #[doc = #archived_doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[repr(transparent)]
+        #vis struct #archived(::rkyv::Archived<#encoded_type>);
+
+        impl #archived {
+            /// Returns the variant this value archives.
+            pub fn to_native(&self) -> #name {
+                #to_native
+            }
+        }
+
+        // SAFETY: a transparent wrapper around an archived integer has the same layout on every platform
+        unsafe impl ::rkyv::Portable for #archived {}
+
+        // SAFETY: a transparent wrapper around an integer has no padding bytes
+        unsafe impl ::rkyv::traits::NoUndef for #archived {}
+
+        // SAFETY: `check_bytes()` only accepts discriminants of the enum's variants
+        unsafe impl<__C> ::rkyv::bytecheck::CheckBytes<__C> for #archived
+        where
+            __C: ::rkyv::rancor::Fallible + ?Sized,
+            __C::Error: ::rkyv::rancor::Source,
+        {
+            unsafe fn check_bytes(value: *const Self, context: &mut __C) -> ::core::result::Result<(), __C::Error> {
+                let _ = (value, context);
+                #check_bytes
+            }
+        }
+
+        impl ::rkyv::Archive for #name {
+            type Archived = #archived;
+            type Resolver = ();
+
+            fn resolve(&self, _: Self::Resolver, out: ::rkyv::Place<Self::Archived>) {
+                out.write(#archived(::rkyv::Archived::<#encoded_type>::from(#discriminant)));
+            }
+        }
+
+        impl<__S: ::rkyv::rancor::Fallible + ?Sized> ::rkyv::Serialize<__S> for #name {
+            fn serialize(&self, _: &mut __S) -> ::core::result::Result<Self::Resolver, __S::Error> {
+                Ok(())
+            }
+        }
+
+        impl<__D: ::rkyv::rancor::Fallible + ?Sized> ::rkyv::Deserialize<#name, __D> for #archived {
+            fn deserialize(&self, _: &mut __D) -> ::core::result::Result<#name, __D::Error> {
+                Ok(self.to_native())
+            }
+        }
+    }
+}
+
+fn generate_scale_impl(
+    name: &syn::Ident,
+    other_variant: &Option<(&Variant, Type)>,
+    infallible: bool,
+    discriminant_type: &Type,
+) -> proc_macro2::TokenStream {
+    let encoded_type = encoded_discriminant_type(other_variant, discriminant_type);
+    let discriminant = encoded_discriminant(name, other_variant, discriminant_type, quote! { self });
+    let from_encoded = encoded_discriminant_constructor(name, other_variant, discriminant_type);
+    let repr = encoded_type.to_token_stream().to_string();
+    // SCALE has no compact form for signed integers, and no encoding for usize and isize at all
+    let (wire_type, compact): (Type, bool) = match repr.as_str() {
+        "usize" => (syn::parse_quote! { u64 }, true),
+        "isize" => (syn::parse_quote! { i64 }, false),
+        _ => (encoded_type.clone(), repr.starts_with('u')),
+    };
+    let wire = if compact {
+        quote! { ::parity_scale_codec::Compact<#wire_type> }
+    } else {
+        quote! { #wire_type }
+    };
+    let (to_wire, from_wire) = if compact {
+        (quote! { ::parity_scale_codec::Compact(#discriminant as #wire_type) }, quote! { value.0 })
+    } else {
+        (quote! { #discriminant as #wire_type }, quote! { value })
+    };
+    let unknown = format!("unknown discriminant for {}", name);
+    let from_discriminant = if infallible {
+        quote! { Ok(#from_encoded(value)) }
+    } else {
+        quote! { #from_encoded(value).ok_or_else(|| #unknown.into()) }
+    };
+
+    quote! {
+        impl ::parity_scale_codec::Encode for #name {
+            fn size_hint(&self) -> usize {
+                ::parity_scale_codec::Encode::size_hint(&(#to_wire))
+            }
+
+            fn encode_to<__T: ::parity_scale_codec::Output + ?Sized>(&self, dest: &mut __T) {
+                ::parity_scale_codec::Encode::encode_to(&(#to_wire), dest)
+            }
+        }
+
+        impl ::parity_scale_codec::EncodeLike for #name {}
+
+        impl ::parity_scale_codec::Decode for #name {
+            fn decode<__I: ::parity_scale_codec::Input>(
+                input: &mut __I,
+            ) -> ::core::result::Result<Self, ::parity_scale_codec::Error> {
+                let value = <#wire as ::parity_scale_codec::Decode>::decode(input)?;
+                let value = <#encoded_type as ::core::convert::TryFrom<#wire_type>>::try_from(#from_wire)
+                    .map_err(|_| #unknown)?;
+                #from_discriminant
+            }
+        }
+    }
+}
+
+/// Converts an integer expression to the narrowest BSON integer holding it, or a decimal string beyond `i64`.
+fn bson_integer(value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    quote! {
+        match (::core::primitive::i32::try_from(#value), ::core::primitive::i64::try_from(#value)) {
+            (Ok(value), _) => ::bson::Bson::Int32(value),
+            (_, Ok(value)) => ::bson::Bson::Int64(value),
+            _ => ::bson::Bson::String(::std::string::ToString::to_string(&#value)),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn generate_bson_impl(
+    name: &syn::Ident,
+    vis: &syn::Visibility,
+    unit_names: &[LitStr],
+    other_variant: &Option<(&Variant, Type)>,
+    infallible: bool,
+    wide_other: bool,
+    discriminant_type: &Type,
+    serde_mode: SerdeMode,
+) -> proc_macro2::TokenStream {
+    let error = format_ident!("{}FromBsonError", name);
+    let error_doc = format!("The error returned when converting a `Bson` value to a [`{}`] fails.", name);
+    let invalid = format!("invalid {} value {{}}{}", name, expected_names(unit_names));
+
+    // Like serde, the name mode writes names and falls back to the raw value for the "other" variant. Bson is
+    // human-readable, so the auto mode writes names too, and integers are read back as discriminants, so the
+    // ordinal mode writes those
+    let unit_value = match serde_mode {
+        SerdeMode::Name | SerdeMode::Auto => quote! { ::bson::Bson::String(::std::string::ToString::to_string(value.name())) },
+        SerdeMode::Discriminant | SerdeMode::Ordinal => bson_integer(quote! { value.discriminant() }),
+    };
+    let to_bson = match other_variant {
+        Some((variant, _)) => {
+            let variant_name = &variant.ident;
+            let other_value = bson_integer(quote! { *val });
+            quote! {
+                match &value {
+                    #name::#variant_name(val) => #other_value,
+                    _ => #unit_value,
+                }
+            }
+        }
+        None => unit_value,
+    };
+
+    // Wide "other" fields accept any value of their own type, not just of the repr
+    let (integer_type, from_integer) = match other_variant {
+        Some((_, other_type)) if wide_other => {
+            (other_type.clone(), quote! { Some(#name::from_wide_discriminant(discr)) })
+        }
+        _ if infallible => (discriminant_type.clone(), quote! { Some(#name::from_discriminant(discr)) }),
+        _ => (discriminant_type.clone(), quote! { #name::from_discriminant(discr) }),
+    };
+
+    quote! {
+        impl From<#name> for ::bson::Bson {
+            fn from(value: #name) -> Self {
+                #to_bson
+            }
+        }
+
+        impl ::core::convert::TryFrom<::bson::Bson> for #name {
+            type Error = #error;
+
+            fn try_from(value: ::bson::Bson) -> ::core::result::Result<Self, Self::Error> {
+                let discr = match &value {
+                    ::bson::Bson::Int32(discr) => <#integer_type>::try_from(*discr).ok(),
+                    ::bson::Bson::Int64(discr) => <#integer_type>::try_from(*discr).ok(),
+                    ::bson::Bson::String(name) => match #name::from_name(name) {
+                        Some(variant) => return Ok(variant),
+                        None => name.parse::<#integer_type>().ok(),
+                    },
+                    _ => None,
+                };
+                discr.and_then(|discr| #from_integer).ok_or(#error { value })
+            }
+        }
+
+        #[doc = #error_doc]
+        #[derive(Debug, Clone)]
+        #vis struct #error {
+            value: ::bson::Bson,
+        }
+
+        impl #error {
+            /// Returns the value that could not be converted.
+            pub fn value(&self) -> &::bson::Bson {
+                &self.value
+            }
+        }
+
+        impl ::core::fmt::Display for #error {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::write!(f, #invalid, self.value)
+            }
+        }
+
+        impl ::std::error::Error for #error {}
+    }
+}
+
+fn generate_sqlx_discriminant_impl(
+    name: &syn::Ident,
+    other_variant: &Option<(&Variant, Type)>,
+    infallible: bool,
+    num_variants: usize,
+    discriminant_type: &Type,
+) -> proc_macro2::TokenStream {
+    let encoded_type = encoded_discriminant_type(other_variant, discriminant_type);
+    let discriminant = encoded_discriminant(name, other_variant, discriminant_type, quote! { self });
+    let from_encoded = encoded_discriminant_constructor(name, other_variant, discriminant_type);
+    let from_discriminant = if infallible {
+        quote! { Ok(#from_encoded(value)) }
+    } else {
+        let (expected, expected_argument) = expected_discriminants(name, num_variants);
+        let unknown = format!("unknown discriminant `{{}}` for {}{}", name, expected);
+        quote! {
+            #from_encoded(value).ok_or_else(|| ::std::format!(#unknown, value #expected_argument).into())
+        }
+    };
+
+    quote! {
+        impl<__DB: ::sqlx::Database> ::sqlx::Type<__DB> for #name
+        where
+            #encoded_type: ::sqlx::Type<__DB>,
+        {
+            fn type_info() -> <__DB as ::sqlx::Database>::TypeInfo {
+                <#encoded_type as ::sqlx::Type<__DB>>::type_info()
+            }
+
+            fn compatible(ty: &<__DB as ::sqlx::Database>::TypeInfo) -> bool {
+                <#encoded_type as ::sqlx::Type<__DB>>::compatible(ty)
+            }
+        }
+
+        impl<'q, __DB: ::sqlx::Database> ::sqlx::Encode<'q, __DB> for #name
+        where
+            #encoded_type: ::sqlx::Encode<'q, __DB>,
+        {
+            fn encode_by_ref(
+                &self,
+                buf: &mut <__DB as ::sqlx::Database>::ArgumentBuffer,
+            ) -> ::core::result::Result<::sqlx::encode::IsNull, ::sqlx::error::BoxDynError> {
+                <#encoded_type as ::sqlx::Encode<'q, __DB>>::encode(#discriminant, buf)
+            }
+        }
+
+        impl<'r, __DB: ::sqlx::Database> ::sqlx::Decode<'r, __DB> for #name
+        where
+            #encoded_type: ::sqlx::Decode<'r, __DB>,
+        {
+            fn decode(
+                value: <__DB as ::sqlx::Database>::ValueRef<'r>,
+            ) -> ::core::result::Result<Self, ::sqlx::error::BoxDynError> {
+                let value = <#encoded_type as ::sqlx::Decode<'r, __DB>>::decode(value)?;
+                #from_discriminant
+            }
+        }
+    }
+}
+
+/// Maps a repr to the diesel SQL type available on every backend, if there is one.
+fn diesel_sql_type(discriminant_type: &Type) -> Option<proc_macro2::TokenStream> {
+    let sql_type = match discriminant_type.to_token_stream().to_string().as_str() {
+        "i8" => quote! { TinyInt },
+        "i16" => quote! { SmallInt },
+        "i32" => quote! { Integer },
+        "i64" => quote! { BigInt },
+        _ => return None,
+    };
+    Some(quote! { ::diesel::sql_types::#sql_type })
+}
+
+fn generate_diesel_name_impl(
+    name: &syn::Ident,
+    unit_names: &[LitStr],
+) -> proc_macro2::TokenStream {
+    let unknown = format!("unknown variant `{{}}` for {}{}", name, expected_names(unit_names));
+
+    quote! {
+        impl<__DB> ::diesel::serialize::ToSql<::diesel::sql_types::Text, __DB> for #name
+        where
+            __DB: ::diesel::backend::Backend,
+            str: ::diesel::serialize::ToSql<::diesel::sql_types::Text, __DB>,
+        {
+            fn to_sql<'b>(&'b self, out: &mut ::diesel::serialize::Output<'b, '_, __DB>) -> ::diesel::serialize::Result {
+                <str as ::diesel::serialize::ToSql<::diesel::sql_types::Text, __DB>>::to_sql(self.name(), out)
+            }
+        }
+
+        impl<__DB> ::diesel::deserialize::FromSql<::diesel::sql_types::Text, __DB> for #name
+        where
+            __DB: ::diesel::backend::Backend,
+            ::std::string::String: ::diesel::deserialize::FromSql<::diesel::sql_types::Text, __DB>,
+        {
+            fn from_sql(bytes: <__DB as ::diesel::backend::Backend>::RawValue<'_>) -> ::diesel::deserialize::Result<Self> {
+                let value = <::std::string::String as ::diesel::deserialize::FromSql<::diesel::sql_types::Text, __DB>>::from_sql(bytes)?;
+                #name::from_name(&value).ok_or_else(|| ::std::format!(#unknown, value).into())
+            }
+        }
+    }
+}
+
+fn generate_diesel_discriminant_impl(
+    name: &syn::Ident,
+    unit_variants: &[&Variant],
+    other_variant: &Option<(&Variant, Type)>,
+    infallible: bool,
+    discriminant_type: &Type,
+    discriminants: &[Expr],
+    sql_type: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let scrutinee = self_scrutinee(other_variant);
+    let num_variants = unit_variants.len();
+    let to_sql = quote! { <#discriminant_type as ::diesel::serialize::ToSql<#sql_type, __DB>>::to_sql };
+    // Diesel borrows the value for the whole query, so unit variants hand it a constant
+    let to_sql_arms = unit_variants.iter().zip(discriminants).map(|(variant, discriminant)| {
+        let variant_name = &variant.ident;
+        quote! {
+            #name::#variant_name => {
+                const __DISCRIMINANT: #discriminant_type = #discriminant as #discriminant_type;
+                #to_sql(&__DISCRIMINANT, out)
+            }
+        }
+    });
+
+    let other_arm = other_variant.as_ref().map(|(variant, _)| {
+        let variant_name = &variant.ident;
+        quote! { #name::#variant_name(val) => #to_sql(val, out), }
+    });
+
+    let from_discriminant = if infallible {
+        quote! { Ok(#name::from_discriminant(value)) }
+    } else {
+        let (expected, expected_argument) = expected_discriminants(name, num_variants);
+        let unknown = format!("unknown discriminant `{{}}` for {}{}", name, expected);
+        quote! {
+            #name::from_discriminant(value).ok_or_else(|| ::std::format!(#unknown, value #expected_argument).into())
+        }
+    };
+
+    quote! {
+        impl<__DB> ::diesel::serialize::ToSql<#sql_type, __DB> for #name
+        where
+            __DB: ::diesel::backend::Backend,
+            #discriminant_type: ::diesel::serialize::ToSql<#sql_type, __DB>,
+        {
+            fn to_sql<'b>(&'b self, out: &mut ::diesel::serialize::Output<'b, '_, __DB>) -> ::diesel::serialize::Result {
+                match #scrutinee {
+                    #(#to_sql_arms,)*
+                    #other_arm
+                }
+            }
+        }
+
+        impl<__DB> ::diesel::deserialize::FromSql<#sql_type, __DB> for #name
+        where
+            __DB: ::diesel::backend::Backend,
+            #discriminant_type: ::diesel::deserialize::FromSql<#sql_type, __DB>,
+        {
+            fn from_sql(bytes: <__DB as ::diesel::backend::Backend>::RawValue<'_>) -> ::diesel::deserialize::Result<Self> {
+                let value = <#discriminant_type as ::diesel::deserialize::FromSql<#sql_type, __DB>>::from_sql(bytes)?;
+                #from_discriminant
+            }
+        }
+    }
+}
+
+/// Whether postgres-types can store the repr, which needs a matching Postgres type.
+fn has_postgres_type(discriminant_type: &Type) -> bool {
+    matches!(discriminant_type.to_token_stream().to_string().as_str(), "i8" | "i16" | "i32" | "i64" | "u32")
+}
+
+fn generate_postgres_name_impl(
+    name: &syn::Ident,
+    unit_variants: &[&Variant],
+    unit_names: &[LitStr],
+    other_variant: &Option<(&Variant, Type)>,
+    discriminant_type: &Type,
+) -> proc_macro2::TokenStream {
+    let scrutinee = self_scrutinee(other_variant);
+    let to_sql_arms = unit_variants.iter().zip(unit_names).map(|(variant, variant_str)| {
+        let variant_name = &variant.ident;
+        quote! { #name::#variant_name => <&str as ::postgres_types::ToSql>::to_sql(&#variant_str, ty, out) }
+    });
+
+    // Like the serde feature, the "other" variant is stored as its raw value in decimal
+    let other_arm = other_variant.as_ref().map(|(variant, _)| {
+        let variant_name = &variant.ident;
+        quote! {
+            #name::#variant_name(val) => {
+                <::std::string::String as ::postgres_types::ToSql>::to_sql(&val.to_string(), ty, out)
+            }
+        }
+    });
+    let parse_other = other_variant.as_ref().map(|(_, other_type)| {
+        let from_other = other_value_constructor(name, other_type, discriminant_type);
+        quote! { .or_else(|| value.parse::<#other_type>().ok().map(#from_other)) }
+    });
+    let unknown = format!("unknown variant `{{}}` for {}{}", name, expected_names(unit_names));
+
+    // The buffer type comes from the private re-export that postgres-types' own derive uses
+    quote! {
+        impl ::postgres_types::ToSql for #name {
+            fn to_sql(
+                &self,
+                ty: &::postgres_types::Type,
+                out: &mut ::postgres_types::private::BytesMut,
+            ) -> ::core::result::Result<::postgres_types::IsNull, ::std::boxed::Box<dyn ::std::error::Error + Sync + Send>> {
+                match #scrutinee {
+                    #(#to_sql_arms,)*
+                    #other_arm
+                }
+            }
+
+            fn accepts(ty: &::postgres_types::Type) -> bool {
+                <&str as ::postgres_types::ToSql>::accepts(ty)
+            }
+
+            ::postgres_types::to_sql_checked!();
+        }
+
+        impl<'a> ::postgres_types::FromSql<'a> for #name {
+            fn from_sql(
+                ty: &::postgres_types::Type,
+                raw: &'a [u8],
+            ) -> ::core::result::Result<Self, ::std::boxed::Box<dyn ::std::error::Error + Sync + Send>> {
+                let value = <&str as ::postgres_types::FromSql>::from_sql(ty, raw)?;
+                #name::from_name(value)
+                    #parse_other
+                    .ok_or_else(|| ::std::format!(#unknown, value).into())
+            }
+
+            fn accepts(ty: &::postgres_types::Type) -> bool {
+                <&str as ::postgres_types::FromSql>::accepts(ty)
+            }
+        }
+    }
+}
+
+fn generate_postgres_discriminant_impl(
+    name: &syn::Ident,
+    other_variant: &Option<(&Variant, Type)>,
+    infallible: bool,
+    num_variants: usize,
+    discriminant_type: &Type,
+) -> proc_macro2::TokenStream {
+    let encoded_type = encoded_discriminant_type(other_variant, discriminant_type);
+    let discriminant = encoded_discriminant(name, other_variant, discriminant_type, quote! { self });
+    let from_encoded = encoded_discriminant_constructor(name, other_variant, discriminant_type);
+    let from_discriminant = if infallible {
+        quote! { Ok(#from_encoded(value)) }
+    } else {
+        let (expected, expected_argument) = expected_discriminants(name, num_variants);
+        let unknown = format!("unknown discriminant `{{}}` for {}{}", name, expected);
+        quote! {
+            #from_encoded(value).ok_or_else(|| ::std::format!(#unknown, value #expected_argument).into())
+        }
+    };
+
+    quote! {
+        impl ::postgres_types::ToSql for #name {
+            fn to_sql(
+                &self,
+                ty: &::postgres_types::Type,
+                out: &mut ::postgres_types::private::BytesMut,
+            ) -> ::core::result::Result<::postgres_types::IsNull, ::std::boxed::Box<dyn ::std::error::Error + Sync + Send>> {
+                <#encoded_type as ::postgres_types::ToSql>::to_sql(&#discriminant, ty, out)
+            }
+
+            fn accepts(ty: &::postgres_types::Type) -> bool {
+                <#encoded_type as ::postgres_types::ToSql>::accepts(ty)
+            }
+
+            ::postgres_types::to_sql_checked!();
+        }
+
+        impl<'a> ::postgres_types::FromSql<'a> for #name {
+            fn from_sql(
+                ty: &::postgres_types::Type,
+                raw: &'a [u8],
+            ) -> ::core::result::Result<Self, ::std::boxed::Box<dyn ::std::error::Error + Sync + Send>> {
+                let value = <#encoded_type as ::postgres_types::FromSql>::from_sql(ty, raw)?;
+                #from_discriminant
+            }
+
+            fn accepts(ty: &::postgres_types::Type) -> bool {
+                <#encoded_type as ::postgres_types::FromSql>::accepts(ty)
+            }
+        }
+    }
+}
+
+/// Whether the repr converts losslessly into `i32`, the wire type of protobuf enums.
+fn fits_in_i32(discriminant_type: &Type) -> bool {
+    matches!(discriminant_type.to_token_stream().to_string().as_str(), "i8" | "i16" | "i32" | "u8" | "u16")
+}
+
+fn generate_prost_impl(
+    name: &syn::Ident,
+    other_variant: &Option<(&Variant, Type)>,
+    infallible: bool,
+    discriminant_type: &Type,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let encoded_type = encoded_discriminant_type(other_variant, discriminant_type);
+    let discriminant = encoded_discriminant(name, other_variant, discriminant_type, quote! { value });
+    let from_encoded = encoded_discriminant_constructor(name, other_variant, discriminant_type);
+    // Unknown values land in the "other" variant when there is one, as long as they fit its field
+    let from_discriminant = if infallible {
+        quote! { .map(#from_encoded) }
+    } else {
+        quote! { .and_then(#name::from_discriminant) }
+    };
+
+    let from_i32_impl = quote! {
+        /// Converts a protobuf enum value into the corresponding variant, like prost's generated `from_i32`.
+        ///
+        /// Values that don't match a unit variant map to the "other" or fallback variant if present and in range
+        /// for the "other" field or repr, otherwise `None` is returned.
+        ///
+        /// # Examples
+        ///
+        /// ```ignore
+        /// # use unit_enum::UnitEnum;
+        /// #[derive(UnitEnum)]
+        /// enum Example {
+        ///     A,
+        ///     B = 5,
+        /// }
+        ///
+        /// assert_eq!(Example::from_i32(5), Some(Example::B));
+        /// assert_eq!(Example::from_i32(1), None);
+        /// ```
+        pub fn from_i32(value: i32) -> Option<Self> {
+            <#encoded_type as ::core::convert::TryFrom<i32>>::try_from(value)
+                .ok()
+                #from_discriminant
+        }
+    };
+
+    let prost_impl = quote! {
+        impl ::core::convert::From<#name> for i32 {
+            fn from(value: #name) -> Self {
+                i32::from(#discriminant)
+            }
+        }
+
+        impl ::core::convert::TryFrom<i32> for #name {
+            type Error = ::prost::UnknownEnumValue;
+
+            fn try_from(value: i32) -> ::core::result::Result<Self, Self::Error> {
+                #name::from_i32(value).ok_or(::prost::UnknownEnumValue(value))
+            }
+        }
+    };
+
+    (from_i32_impl, prost_impl)
+}
+
+fn generate_num_enum_impl(
+    name: &syn::Ident,
+    infallible: bool,
+    wide_other: bool,
+    prost_conversions: bool,
+    discriminant_type: &Type,
+) -> proc_macro2::TokenStream {
+    let type_name = name.unraw().to_string();
+    // A wide "other" value doesn't always fit in the repr
+    let into_primitive = (!wide_other && !prost_conversions).then(|| quote! {
+        impl ::core::convert::From<#name> for #discriminant_type {
+            fn from(value: #name) -> Self {
+                value.discriminant()
+            }
+        }
+    });
+
+    let from_primitive = if infallible {
+        let from_impl = (!prost_conversions).then(|| quote! {
+            impl ::core::convert::From<#discriminant_type> for #name {
+                fn from(number: #discriminant_type) -> Self {
+                    #name::from_discriminant(number)
+                }
+            }
+        });
+        quote! {
+            impl ::num_enum::FromPrimitive for #name {
+                type Primitive = #discriminant_type;
+
+                fn from_primitive(number: Self::Primitive) -> Self {
+                    #name::from_discriminant(number)
+                }
+            }
+
+            impl ::num_enum::TryFromPrimitive for #name {
+                type Primitive = #discriminant_type;
+                type Error = ::core::convert::Infallible;
+
+                const NAME: &'static str = #type_name;
+
+                fn try_from_primitive(number: Self::Primitive) -> ::core::result::Result<Self, Self::Error> {
+                    Ok(#name::from_discriminant(number))
+                }
+            }
+
+            #from_impl
+        }
+    } else {
+        let try_from_impl = (!prost_conversions).then(|| quote! {
+            impl ::core::convert::TryFrom<#discriminant_type> for #name {
+                type Error = ::num_enum::TryFromPrimitiveError<Self>;
+
+                fn try_from(number: #discriminant_type) -> ::core::result::Result<Self, Self::Error> {
+                    <Self as ::num_enum::TryFromPrimitive>::try_from_primitive(number)
+                }
+            }
+        });
+        quote! {
+            impl ::num_enum::TryFromPrimitive for #name {
+                type Primitive = #discriminant_type;
+                type Error = ::num_enum::TryFromPrimitiveError<Self>;
+
+                const NAME: &'static str = #type_name;
+
+                fn try_from_primitive(number: Self::Primitive) -> ::core::result::Result<Self, Self::Error> {
+                    #name::from_discriminant(number).ok_or(::num_enum::TryFromPrimitiveError { number })
+                }
+            }
+
+            #try_from_impl
+
+            impl ::num_enum::UnsafeFromPrimitive for #name {
+                type Primitive = #discriminant_type;
+
+                unsafe fn unchecked_transmute_from(number: Self::Primitive) -> Self {
+                    // SAFETY: the caller guarantees that `number` is the discriminant of a variant.
+                    unsafe { #name::from_discriminant_unchecked(number) }
+                }
+            }
+        }
+    };
+
+    quote! {
+        #into_primitive
+
+        #from_primitive
+    }
+}
+
+/// Whether napi-rs converts the repr to a JavaScript number.
+fn has_napi_number(discriminant_type: &Type) -> bool {
+    matches!(
+        discriminant_type.to_token_stream().to_string().as_str(),
+        "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32"
+    )
+}
+
+fn generate_napi_name_impl(
+    name: &syn::Ident,
+    unit_names: &[LitStr],
+    other_variant: &Option<(&Variant, Type)>,
+    discriminant_type: &Type,
+) -> proc_macro2::TokenStream {
+    let name_str = name.to_string();
+
+    // Like the serde feature, the "other" variant converts to its raw value in decimal
+    let (to_string, parse_other) = match other_variant {
+        Some((variant, other_type)) => {
+            let variant_name = &variant.ident;
+            let from_other = other_value_constructor(name, other_type, discriminant_type);
+            (
+                quote! {
+                    match val {
+                        #name::#variant_name(val) => val.to_string(),
+                        _ => val.name().to_owned(),
+                    }
+                },
+                Some(quote! { .or_else(|| value.parse::<#other_type>().ok().map(#from_other)) }),
+            )
+        }
+        None => (quote! { val.name() }, None),
+    };
+    let unknown = format!("unknown variant `{{}}` for {}{}", name, expected_names(unit_names));
+
+    quote! {
+        impl ::napi::bindgen_prelude::TypeName for #name {
+            fn type_name() -> &'static str {
+                #name_str
+            }
+
+            fn value_type() -> ::napi::ValueType {
+                ::napi::ValueType::String
+            }
+        }
+
+        impl ::napi::bindgen_prelude::ValidateNapiValue for #name {}
+
+        impl ::napi::bindgen_prelude::ToNapiValue for #name {
+            unsafe fn to_napi_value(
+                env: ::napi::sys::napi_env,
+                val: Self,
+            ) -> ::napi::Result<::napi::sys::napi_value> {
+                unsafe { ::napi::bindgen_prelude::ToNapiValue::to_napi_value(env, #to_string) }
+            }
+        }
+
+        impl ::napi::bindgen_prelude::FromNapiValue for #name {
+            unsafe fn from_napi_value(
+                env: ::napi::sys::napi_env,
+                napi_val: ::napi::sys::napi_value,
+            ) -> ::napi::Result<Self> {
+                let value = unsafe {
+                    <::std::string::String as ::napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, napi_val)?
+                };
+                #name::from_name(&value)
+                    #parse_other
+                    .ok_or_else(|| ::napi::Error::new(::napi::Status::InvalidArg, ::std::format!(#unknown, value)))
+            }
+        }
+    }
+}
+
+fn generate_napi_discriminant_impl(
+    name: &syn::Ident,
+    other_variant: &Option<(&Variant, Type)>,
+    infallible: bool,
+    num_variants: usize,
+    discriminant_type: &Type,
+) -> proc_macro2::TokenStream {
+    let name_str = name.to_string();
+    let encoded_type = encoded_discriminant_type(other_variant, discriminant_type);
+    let discriminant = encoded_discriminant(name, other_variant, discriminant_type, quote! { val });
+    let from_encoded = encoded_discriminant_constructor(name, other_variant, discriminant_type);
+
+    let from_discriminant = if infallible {
+        quote! { Ok(#from_encoded(value)) }
+    } else {
+        let (expected, expected_argument) = expected_discriminants(name, num_variants);
+        let unknown = format!("unknown discriminant `{{}}` for {}{}", name, expected);
+        quote! {
+            #from_encoded(value)
+                .ok_or_else(|| ::napi::Error::new(::napi::Status::InvalidArg, ::std::format!(#unknown, value #expected_argument)))
+        }
+    };
+
+    quote! {
+        impl ::napi::bindgen_prelude::TypeName for #name {
+            fn type_name() -> &'static str {
+                #name_str
             }
 
-            #values_impl
+            fn value_type() -> ::napi::ValueType {
+                ::napi::ValueType::Number
+            }
         }
-    }.into()
+
+        impl ::napi::bindgen_prelude::ValidateNapiValue for #name {}
+
+        impl ::napi::bindgen_prelude::ToNapiValue for #name {
+            unsafe fn to_napi_value(
+                env: ::napi::sys::napi_env,
+                val: Self,
+            ) -> ::napi::Result<::napi::sys::napi_value> {
+                unsafe {
+                    <#encoded_type as ::napi::bindgen_prelude::ToNapiValue>::to_napi_value(env, #discriminant)
+                }
+            }
+        }
+
+        impl ::napi::bindgen_prelude::FromNapiValue for #name {
+            unsafe fn from_napi_value(
+                env: ::napi::sys::napi_env,
+                napi_val: ::napi::sys::napi_value,
+            ) -> ::napi::Result<Self> {
+                let value = unsafe {
+                    <#encoded_type as ::napi::bindgen_prelude::FromNapiValue>::from_napi_value(env, napi_val)?
+                };
+                #from_discriminant
+            }
+        }
+    }
 }
 
-fn generate_name_impl(
+/// Whether uniffi has a fixed-width integer type matching the repr.
+fn has_uniffi_integer(discriminant_type: &Type) -> bool {
+    matches!(
+        discriminant_type.to_token_stream().to_string().as_str(),
+        "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64"
+    )
+}
+
+fn generate_uniffi_impl(
     name: &syn::Ident,
-    unit_variants: &[&Variant],
+    vis: &syn::Visibility,
+    unit_names: &[LitStr],
     other_variant: &Option<(&Variant, Type)>,
+    infallible: bool,
+    discriminant_type: &Type,
 ) -> proc_macro2::TokenStream {
-    let unit_match_arms = unit_variants.iter().map(|variant| {
-        let variant_name = &variant.ident;
-        quote! { #name::#variant_name => stringify!(#variant_name) }
-    });
+    let num_variants = unit_names.len();
+    let encoded_type = encoded_discriminant_type(other_variant, discriminant_type);
+    let discriminant = encoded_discriminant(name, other_variant, discriminant_type, quote! { value });
+    let from_encoded = encoded_discriminant_constructor(name, other_variant, discriminant_type);
+    let try_lift = if infallible {
+        quote! { |value| Ok(#from_encoded(value)) }
+    } else {
+        let (expected, expected_argument) = expected_discriminants(name, num_variants);
+        let unknown = format!("unknown discriminant `{{}}` for {}{}", name, expected);
+        quote! {
+            |value| #from_encoded(value)
+                .ok_or_else(|| ::uniffi::deps::anyhow::anyhow!(#unknown, value #expected_argument))
+        }
+    };
 
-    let other_arm = other_variant.as_ref().map(|(variant, _)| {
-        let variant_name = &variant.ident;
-        quote! { #name::#variant_name(_) => stringify!(#variant_name) }
-    });
+    // Foreign code only sees the integer, so the names are exported alongside it
+    let names_fn = syn::Ident::new(
+        &format!("{}_variant_names", StrumCase::Snake.apply(&name.unraw().to_string())),
+        name.span(),
+    );
+    let names_doc = format!("Returns the names of the unit variants of [`{}`], in declaration order.", name);
 
     quote! {
-        /// Returns the name of the enum variant as a string.
-        ///
-        /// # Examples
-        ///
-        /// ```ignore
-        /// # use unit_enum::UnitEnum;
-        /// #[derive(UnitEnum)]
-        /// enum Example {
-        ///     A,
-        ///     B = 10,
-        ///     C,
-        /// }
-        ///
-        /// assert_eq!(Example::A.name(), "A");
-        /// assert_eq!(Example::B.name(), "B");
-        /// assert_eq!(Example::C.name(), "C");
-        /// ```
-        pub fn name(&self) -> &str {
-            match self {
-                #(#unit_match_arms,)*
-                #other_arm
-            }
+        ::uniffi::custom_type!(#name, #encoded_type, {
+            lower: |value| #discriminant,
+            try_lift: #try_lift,
+        });
+
+        #[doc = #names_doc]
+        #[::uniffi::export]
+        #vis fn #names_fn() -> ::std::vec::Vec<::std::string::String> {
+            ::std::vec![#(::std::string::String::from(#unit_names)),*]
         }
     }
 }
 
-fn generate_ordinal_impl(
+fn generate_info_impl(
     name: &syn::Ident,
     unit_variants: &[&Variant],
-    other_variant: &Option<(&Variant, Type)>,
-    num_variants: usize,
+    unit_names: &[LitStr],
+    module: &syn::Path,
+    discriminant_type: &Type,
 ) -> proc_macro2::TokenStream {
-    let unit_match_arms = unit_variants.iter().enumerate().map(|(index, variant)| {
-        let variant_name = &variant.ident;
-        quote! { #name::#variant_name => #index }
-    });
-
-    let other_arm = other_variant.as_ref().map(|(variant, _)| {
-        let variant_name = &variant.ident;
-        quote! { #name::#variant_name(_) => #num_variants }
+    let type_name = name.unraw().to_string();
+    let repr_name = discriminant_type.to_token_stream().to_string();
+    let variants = unit_variants.iter().zip(unit_names).enumerate().map(|(ordinal, (variant, variant_name))| {
+        let variant_ident = &variant.ident;
+        quote! {
+            #module::VariantInfo {
+                name: #variant_name,
+                discriminant: #name::#variant_ident.discriminant() as i128,
+                ordinal: #ordinal,
+            }
+        }
     });
 
     quote! {
-        /// Returns the zero-based ordinal of the enum variant.
-        ///
-        /// For enums with an "other" variant, it returns the position after all unit variants.
+        /// Returns the name and repr of the enum with the name, discriminant and ordinal of each unit variant.
         ///
         /// # Examples
         ///
         /// ```ignore
         /// # use unit_enum::UnitEnum;
         /// #[derive(UnitEnum)]
+        /// #[repr(u8)]
+        /// #[unit_enum(info = crate::reflect)]
         /// enum Example {
-        ///     A,      // ordinal: 0
-        ///     B = 10, // ordinal: 1
-        ///     C,      // ordinal: 2
+        ///     A = 1,
+        ///     B = 2,
         /// }
         ///
-        /// assert_eq!(Example::A.ordinal(), 0);
-        /// assert_eq!(Example::B.ordinal(), 1);
-        /// assert_eq!(Example::C.ordinal(), 2);
+        /// let info = Example::info();
+        /// assert_eq!(info.name, "Example");
+        /// assert_eq!(info.repr, "u8");
+        /// assert_eq!(info.variants[1].discriminant, 2);
         /// ```
-        pub fn ordinal(&self) -> usize {
-            match self {
-                #(#unit_match_arms,)*
-                #other_arm
-            }
+        pub fn info() -> &'static #module::EnumInfo {
+            static __INFO: #module::EnumInfo = #module::EnumInfo {
+                name: #type_name,
+                repr: #repr_name,
+                variants: &[#(#variants),*],
+            };
+            &__INFO
         }
     }
 }
-fn generate_from_ordinal_impl(
+
+/// Quotes a string for JSON.
+fn json_string(value: &str) -> String {
+    let mut json = String::from('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn generate_schema_impl(
     name: &syn::Ident,
     unit_variants: &[&Variant],
+    unit_names: &[LitStr],
+    other_name: &Option<LitStr>,
+    discriminant_type: &Type,
 ) -> proc_macro2::TokenStream {
-    let match_arms = unit_variants.iter().enumerate().map(|(index, variant)| {
+    // Everything but the discriminants is known here, so it goes into the format string with its braces escaped
+    let escape = |text: String| text.replace('{', "{{").replace('}', "}}");
+    let variants = unit_names.iter()
+        .map(|variant_name| {
+            escape(format!(r#"{{"name":{},"discriminant":"#, json_string(&variant_name.value()))) + "{}}}"
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    let other = other_name.as_ref().map_or_else(|| "null".to_owned(), |other_name| json_string(&other_name.value()));
+    let format = escape(format!(r#"{{"name":{},"repr":{},"variants":["#,
+                                json_string(&name.unraw().to_string()),
+                                json_string(&discriminant_type.to_token_stream().to_string())))
+        + &variants
+        + &escape(format!(r#"],"other":{}}}"#, other));
+    let discriminants = unit_variants.iter().map(|variant| {
         let variant_name = &variant.ident;
-        quote! { #index => Some(#name::#variant_name) }
+        quote! { #name::#variant_name.discriminant() }
     });
 
     quote! {
-        /// Converts a zero-based ordinal to an enum variant, if possible.
-        ///
-        /// Returns `Some(variant)` if the ordinal corresponds to a unit variant,
-        /// or `None` if the ordinal is out of range or would correspond to the "other" variant.
+        /// Returns a JSON description of the enum: its name, its repr, the name and discriminant of each unit
+        /// variant in declaration order, and the name of the "other" variant or `null`.
         ///
         /// # Examples
         ///
         /// ```ignore
         /// # use unit_enum::UnitEnum;
-        /// # #[derive(Debug, PartialEq)]
         /// #[derive(UnitEnum)]
+        /// #[repr(u8)]
+        /// #[unit_enum(schema)]
         /// enum Example {
-        ///     A,
-        ///     B,
-        ///     #[unit_enum(other)]
-        ///     Other(i32),
+        ///     A = 1,
+        ///     B = 2,
         /// }
         ///
-        /// assert_eq!(Example::from_ordinal(0), Some(Example::A));
-        /// assert_eq!(Example::from_ordinal(2), None); // Other variant
-        /// assert_eq!(Example::from_ordinal(99), None); // Out of range
+        /// assert_eq!(
+        ///     Example::schema_json(),
+        ///     r#"{"name":"Example","repr":"u8","variants":[{"name":"A","discriminant":1},{"name":"B","discriminant":2}],"other":null}"#,
+        /// );
         /// ```
-        pub fn from_ordinal(ord: usize) -> Option<Self> {
-            match ord {
-                #(#match_arms,)*
-                _ => None
-            }
+        pub fn schema_json() -> ::std::string::String {
+            ::std::format!(#format, #(#discriminants),*)
         }
     }
 }
 
-fn generate_discriminant_impl(
+fn generate_inventory_impl(
     name: &syn::Ident,
     unit_variants: &[&Variant],
-    other_variant: &Option<(&Variant, Type)>,
-    discriminant_type: &Type,
-    discriminants: &[Expr],
+    unit_names: &[LitStr],
+    entry: &syn::Path,
 ) -> proc_macro2::TokenStream {
-    let unit_match_arms = unit_variants.iter().zip(discriminants).map(|(variant, discriminant)| {
-        let variant_name = &variant.ident;
-        quote! { #name::#variant_name => #discriminant as #discriminant_type }
-    });
-
-    let other_arm = other_variant.as_ref().map(|(variant, _)| {
-        let variant_name = &variant.ident;
-        quote! { #name::#variant_name(val) => *val }
+    let type_name = format!("::{}", name.unraw());
+    // A single integer type lets one registry hold enums of every repr
+    let variants = unit_variants.iter().zip(unit_names).map(|(variant, variant_name)| {
+        let variant_ident = &variant.ident;
+        quote! { (#variant_name, #name::#variant_ident.discriminant() as i128) }
     });
 
     quote! {
-        /// Returns the discriminant value of the enum variant.
-        ///
-        /// For "other" variants, returns the contained value.
-        ///
-        /// # Examples
-        ///
-        /// ```ignore
-        /// # use unit_enum::UnitEnum;
-        /// #[derive(UnitEnum)]
-        /// enum Example {
-        ///     A,      // 0
-        ///     B = 10, // 10
-        ///     C,      // 11
-        /// }
-        ///
-        /// assert_eq!(Example::A.discriminant(), 0);
-        /// assert_eq!(Example::B.discriminant(), 10);
-        /// assert_eq!(Example::C.discriminant(), 11);
-        /// ```
-         pub fn discriminant(&self) -> #discriminant_type {
-            match self {
-                #(#unit_match_arms,)*
-                #other_arm
-            }
+        ::inventory::submit! {
+            #entry::new(::core::concat!(::core::module_path!(), #type_name), &[#(#variants),*])
         }
     }
 }
 
-fn generate_from_discriminant_impl(
+fn generate_any_trait_impl(
     name: &syn::Ident,
-    unit_variants: &[&Variant],
+    unit_names: &[LitStr],
     other_variant: &Option<(&Variant, Type)>,
+    any_trait: &syn::Path,
     discriminant_type: &Type,
-    discriminants: &[Expr],
 ) -> proc_macro2::TokenStream {
-    if let Some((other_variant, _)) = other_variant {
-        let match_arms = unit_variants.iter().zip(discriminants).map(|(variant, discriminant)| {
-            let variant_name = &variant.ident;
-            quote! { x if x == (#discriminant as #discriminant_type) => #name::#variant_name }
-        });
-
-        let other_name = &other_variant.ident;
-        quote! {
-            /// Converts a discriminant value to an enum variant.
-            ///
-            /// For enums with an "other" variant, this will always return a value,
-            /// using the "other" variant for undefined discriminants.
-            ///
-            /// # Examples
-            ///
-            /// ```ignore
-            /// # use unit_enum::UnitEnum;
-            /// #[derive(UnitEnum, PartialEq, Debug)]
-            /// #[repr(u8)]
-            /// enum Example {
-            ///     A,      // 0
-            ///     B = 10, // 10
-            ///     #[unit_enum(other)]
-            ///     Other(u8),
-            /// }
-            ///
-            /// assert_eq!(Example::from_discriminant(0), Example::A);
-            /// assert_eq!(Example::from_discriminant(10), Example::B);
-            /// assert_eq!(Example::from_discriminant(42), Example::Other(42));
-            /// ```
-            pub fn from_discriminant(discr: #discriminant_type) -> Self {
-                match discr {
-                    #(#match_arms,)*
-                    other => #name::#other_name(other)
+    // discriminant() panics on a wide "other" value that doesn't fit in the repr, but every field type fits in i128
+    let discriminant = match other_variant {
+        Some((variant, other_type)) if is_wide_other(other_type, discriminant_type) => {
+            let other_name = &variant.ident;
+            quote! {
+                match self {
+                    #name::#other_name(value) => *value as i128,
+                    _ => #name::discriminant(self) as i128,
                 }
             }
         }
-    } else {
-        let match_arms = unit_variants.iter().zip(discriminants).map(|(variant, discriminant)| {
-            let variant_name = &variant.ident;
-            quote! { x if x == (#discriminant as #discriminant_type) => Some(#name::#variant_name) }
-        });
+        _ => quote! { #name::discriminant(self) as i128 },
+    };
 
-        quote! {
-            /// Converts a discriminant value to an enum variant, if possible.
-            ///
-            /// Returns `Some(variant)` if the discriminant corresponds to a defined variant,
-            /// or `None` if the discriminant is undefined.
-            ///
-            /// # Examples
-            ///
-            /// ```ignore
-            /// # use unit_enum::UnitEnum;
-            /// #[derive(UnitEnum, PartialEq, Debug)]
-            /// #[repr(u8)]
-            /// enum Example {
-            ///     A,      // 0
-            ///     B = 10, // 10
-            ///     C,      // 11
-            /// }
-            ///
-            /// assert_eq!(Example::from_discriminant(0), Some(Example::A));
-            /// assert_eq!(Example::from_discriminant(10), Some(Example::B));
-            /// assert_eq!(Example::from_discriminant(42), None);
-            /// ```
-            pub fn from_discriminant(discr: #discriminant_type) -> Option<Self> {
-                match discr {
-                    #(#match_arms,)*
-                    _ => None
+    quote! {
+        impl #any_trait for #name {
+            fn name(&self) -> &'static str {
+                #name::name(self)
+            }
+
+            fn ordinal(&self) -> usize {
+                #name::ordinal(self)
+            }
+
+            fn discriminant_i128(&self) -> i128 {
+                #discriminant
+            }
+
+            fn variant_names(&self) -> &'static [&'static str] {
+                &[#(#unit_names),*]
+            }
+
+            fn set_ordinal(&mut self, ordinal: usize) -> bool {
+                match #name::from_ordinal(ordinal) {
+                    Some(value) => {
+                        *self = value;
+                        true
+                    }
+                    None => false,
                 }
             }
         }
     }
 }
 
-fn generate_values_impl(
+#[allow(clippy::too_many_arguments)]
+fn generate_extern_c_impl(
     name: &syn::Ident,
     unit_variants: &[&Variant],
+    unit_names: &[LitStr],
+    other_variant: &Option<(&Variant, Type)>,
+    other_name: &Option<LitStr>,
+    discriminant_type: &Type,
     discriminants: &[Expr],
-    _other_variant: &Option<(&Variant, Type)>,
+    extern_c: &ExternC,
 ) -> proc_macro2::TokenStream {
-    // Create a vector of variant expressions paired with their discriminants
-    let variant_exprs = unit_variants.iter().zip(discriminants).map(|(variant, discriminant)| {
+    let prefix = extern_c.prefix.as_ref().map_or_else(
+        || StrumCase::Snake.apply(&name.unraw().to_string()),
+        |prefix| prefix.to_string(),
+    );
+    // The repr may be written as a path, like `::core::primitive::u16`
+    let repr = match discriminant_type {
+        Type::Path(path) => path.path.segments.last().map(|segment| segment.ident.to_string()),
+        _ => None,
+    }
+    .unwrap_or_else(|| discriminant_type.to_token_stream().to_string());
+    let from_repr_fn = format_ident!("{}_from_{}", prefix, repr);
+    let from_name_fn = format_ident!("{}_from_name", prefix);
+    let name_fn = format_ident!("{}_name", prefix);
+    let sentinel = extern_c.sentinel.as_ref()
+        .map_or_else(|| quote! { #discriminant_type::MAX }, |sentinel| quote! { #sentinel });
+
+    // C wants NUL-terminated strings, so each name gets its own static copy with the terminator
+    let c_string = |variant_str: &LitStr| LitStr::new(&format!("{}\0", variant_str.value()), variant_str.span());
+    let name_arms = unit_variants.iter().zip(unit_names).map(|(variant, variant_str)| {
         let variant_name = &variant.ident;
-        quote! {
-            #name::#variant_name // The variant
-        }
+        let c_name = c_string(variant_str);
+        quote! { #name::#variant_name => #c_name.as_ptr().cast() }
     });
 
-    // Collect variants into a Vec to ensure consistent ordering
-    quote! {
-        /// Returns an iterator over all unit variants of the enum.
-        ///
-        /// Note: This does not include values from the "other" variant, if present.
-        ///
-        /// # Examples
-        ///
-        /// ```ignore
-        /// # use unit_enum::UnitEnum;
-        /// #[derive(UnitEnum, PartialEq, Debug)]
-        /// enum Example {
-        ///     A,
-        ///     B,
-        ///     #[unit_enum(other)]
-        ///     Other(i32),
-        /// }
-        ///
-        /// let values: Vec<_> = Example::values().collect();
-        /// assert_eq!(values, vec![Example::A, Example::B]);
-        /// ```
-        pub fn values() -> impl Iterator<Item = Self> {
-            vec![
-                #(#variant_exprs),*
-            ].into_iter()
+    let (from_repr_body, name_body) = match other_variant.as_ref().zip(other_name.as_ref()) {
+        Some(((variant, _), variant_str)) => {
+            let variant_name = &variant.ident;
+            let c_name = c_string(variant_str);
+            (
+                quote! { value },
+                quote! {
+                    match #name::from_discriminant(value) {
+                        #(#name_arms,)*
+                        #name::#variant_name(_) => #c_name.as_ptr().cast(),
+                    }
+                },
+            )
         }
+        None => (
+            quote! { #name::ordinal_of_discriminant(value).map_or(__SENTINEL, |_| value) },
+            quote! {
+                match #name::ordinal_of_discriminant(value).and_then(#name::from_ordinal) {
+                    Some(variant) => match variant {
+                        #(#name_arms,)*
+                    },
+                    None => ::core::ptr::null(),
+                }
+            },
+        ),
+    };
+
+    let collision = format!("extern_c sentinel of {} collides with a discriminant", name);
+    let from_repr_doc = format!("Returns `value` if it is a discriminant of [`{}`], or the sentinel otherwise.", name);
+    let from_name_doc = format!("Returns the discriminant of the [`{}`] variant with the given name, or the sentinel.", name);
+    let name_doc = format!("Returns the name of the [`{}`] variant with the given discriminant, or null.", name);
+
+    quote! {
+        const _: () = {
+            const __SENTINEL: #discriminant_type = #sentinel;
+            ::core::assert!(#(#discriminants as #discriminant_type != __SENTINEL &&)* true, #collision);
+
+            #[doc = #from_repr_doc]
+            #[no_mangle]
+            pub extern "C" fn #from_repr_fn(value: #discriminant_type) -> #discriminant_type {
+                #from_repr_body
+            }
+
+            #[doc = #from_name_doc]
+            ///
+            /// # Safety
+            ///
+            /// `name` must be null or point to a NUL-terminated string.
+            #[no_mangle]
+            pub unsafe extern "C" fn #from_name_fn(name: *const ::core::ffi::c_char) -> #discriminant_type {
+                if name.is_null() {
+                    return __SENTINEL;
+                }
+                unsafe { ::core::ffi::CStr::from_ptr(name) }.to_str().ok()
+                    .and_then(#name::from_name)
+                    .map_or(__SENTINEL, |variant| variant.discriminant())
+            }
+
+            #[doc = #name_doc]
+            ///
+            /// The returned string is NUL-terminated and valid for the lifetime of the program.
+            #[no_mangle]
+            pub extern "C" fn #name_fn(value: #discriminant_type) -> *const ::core::ffi::c_char {
+                #name_body
+            }
+        };
     }
-}
\ No newline at end of file
+}